@@ -0,0 +1,227 @@
+//! Runs the debri simulation without a window and prints how long each physics
+//! phase takes.
+//!
+//! cargo run --release --bin headless_bench -- --bodies 10000 --ticks 600 --seed 1 --backend quadtree
+//!
+//! The world layout, starting velocities and `GameRng` come from `--seed`, and
+//! time advances by a fixed step, so two runs with the same arguments simulate
+//! the same scene and print the same state checksum.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use std::time::Duration;
+
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use instant::Instant;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use bevy_ball_game::game::components::Velocity;
+use bevy_ball_game::game::debri::components::{Collider, Debri};
+use bevy_ball_game::game::debri::resources::{DebriKinds, DebriUniverse, FlockParams, QuadBench};
+use bevy_ball_game::game::debri::systems::{
+    build_or_update_quadtree, keep_debri_in_bounds, move_system, update_debri,
+};
+use bevy_ball_game::game::debri::PHYISCS_TICK_RATE;
+use bevy_ball_game::game::resources::{GameRng, WorldBounds};
+use bevy_ball_game::spatial::SpatialBackend;
+
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct BuildPhase;
+
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct SteerPhase;
+
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+struct MovePhase;
+
+struct Options {
+    bodies: usize,
+    ticks: usize,
+    seed: u64,
+    backend: SpatialBackend,
+    world: f32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            bodies: 10_000,
+            ticks: 600,
+            seed: 1,
+            backend: SpatialBackend::QuadTree,
+            world: 4000.0,
+        }
+    }
+}
+
+fn parse_options() -> Options {
+    let mut options = Options::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .unwrap_or_else(|| panic!("missing value for {}", flag));
+        match flag.as_str() {
+            "--bodies" => options.bodies = value.parse().expect("--bodies takes a number"),
+            "--ticks" => options.ticks = value.parse().expect("--ticks takes a number"),
+            "--seed" => options.seed = value.parse().expect("--seed takes a number"),
+            "--world" => options.world = value.parse().expect("--world takes a number"),
+            "--backend" => {
+                options.backend = match value.as_str() {
+                    "quadtree" => SpatialBackend::QuadTree,
+                    "grid" => SpatialBackend::Grid,
+                    "hash" => SpatialBackend::SpatialHash,
+                    other => panic!("unknown backend {}, use quadtree, grid or hash", other),
+                }
+            }
+            other => panic!("unknown flag {}", other),
+        }
+    }
+    options
+}
+
+#[derive(Default)]
+struct PhaseTimes {
+    samples: Vec<Duration>,
+}
+
+impl PhaseTimes {
+    fn time(&mut self, app: &mut App, label: impl ScheduleLabel) {
+        let start = Instant::now();
+        app.world.run_schedule(label);
+        self.samples.push(start.elapsed());
+    }
+
+    fn total(&self) -> Duration {
+        self.samples.iter().sum()
+    }
+
+    fn print(&self, name: &str) {
+        let mut sorted = self.samples.clone();
+        sorted.sort();
+        let average = self.total() / sorted.len().max(1) as u32;
+        let p95 = sorted
+            .get(sorted.len() * 95 / 100)
+            .copied()
+            .unwrap_or_default();
+        println!(
+            "{:<6} avg {:>8.1}us  p95 {:>8.1}us  total {:>8.1}ms",
+            name,
+            average.as_secs_f64() * 1e6,
+            p95.as_secs_f64() * 1e6,
+            self.total().as_secs_f64() * 1e3,
+        );
+    }
+}
+
+fn spawn_bodies(world: &mut World, options: &Options) {
+    let params = FlockParams::default();
+    let kinds = DebriKinds::default();
+    let universe = world.resource::<DebriUniverse>();
+    let (damping, min_speed) = (universe.damping, universe.min_speed);
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    for _ in 0..options.bodies {
+        let position = Vec3::new(
+            rng.gen_range(0.0..options.world),
+            rng.gen_range(0.0..options.world),
+            0.0,
+        );
+        let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+        let kind = kinds.pick(&mut rng);
+        world.spawn((
+            Transform::from_translation(position),
+            Debri,
+            Velocity {
+                value: Vec3::new(angle.cos(), angle.sin(), 0.0) * 200.0,
+                damping,
+                min_speed,
+            },
+            Collider::new(kinds.rules(kind).size / 2.0),
+            params.pick(&mut rng),
+            kind,
+            kinds.rules(kind).bulk(),
+        ));
+    }
+}
+
+// Hash of every body's position and velocity bits, in entity order. Two runs
+// with the same arguments must print the same value.
+fn state_checksum(world: &mut World) -> u64 {
+    let mut bodies: Vec<_> = world
+        .query::<(Entity, &Transform, &Velocity)>()
+        .iter(world)
+        .map(|(entity, transform, velocity)| (entity, transform.translation, velocity.value))
+        .collect();
+    bodies.sort_by_key(|(entity, _, _)| *entity);
+    let mut hasher = DefaultHasher::new();
+    for (entity, position, velocity) in bodies {
+        hasher.write_u64(entity.to_bits());
+        for value in [position.x, position.y, velocity.x, velocity.y] {
+            hasher.write_u32(value.to_bits());
+        }
+    }
+    hasher.finish()
+}
+
+fn main() {
+    let options = parse_options();
+    let tick = Duration::from_secs_f32(1.0 / PHYISCS_TICK_RATE);
+
+    let mut universe = DebriUniverse::new(Vec2::ZERO, Vec2::splat(options.world));
+    universe.set_backend(options.backend);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(tick))
+        .insert_resource(universe)
+        .insert_resource(QuadBench::default())
+        .init_resource::<FlockParams>()
+        .init_resource::<DebriKinds>()
+        .insert_resource(GameRng::new(options.seed))
+        .init_resource::<WorldBounds>()
+        .add_systems(BuildPhase, build_or_update_quadtree)
+        .add_systems(SteerPhase, update_debri)
+        .add_systems(MovePhase, (move_system, keep_debri_in_bounds).chain());
+    app.finish();
+    app.cleanup();
+    spawn_bodies(&mut app.world, &options);
+
+    println!(
+        "{} bodies, {} ticks, seed {}, {:?} backend",
+        options.bodies, options.ticks, options.seed, options.backend
+    );
+
+    let mut build = PhaseTimes::default();
+    let mut steer = PhaseTimes::default();
+    let mut movement = PhaseTimes::default();
+    let start = Instant::now();
+    for _ in 0..options.ticks {
+        // advances `Time` by one fixed tick
+        app.update();
+        build.time(&mut app, BuildPhase);
+        steer.time(&mut app, SteerPhase);
+        movement.time(&mut app, MovePhase);
+    }
+    let elapsed = start.elapsed();
+
+    build.print("build");
+    steer.print("steer");
+    movement.print("move");
+
+    let stats = app.world.resource::<DebriUniverse>().graph.stats();
+    println!(
+        "{} nodes, {} leaves, max depth {}, {:.2} values per leaf",
+        stats.nodes,
+        stats.leaves,
+        stats.max_depth,
+        stats.values_per_leaf()
+    );
+    println!(
+        "{:.1} ticks/s, {:.0} body updates/s",
+        options.ticks as f64 / elapsed.as_secs_f64(),
+        (options.ticks * options.bodies) as f64 / elapsed.as_secs_f64()
+    );
+    println!("state checksum {:016x}", state_checksum(&mut app.world));
+}
@@ -18,7 +18,8 @@ impl Plugin for BuildingPlugin {
             // Events
             .add_event::<EventSpawnBuilding>()
             // Systems
-            .add_systems(FixedUpdate, (spawn_building,))
+            // placed from the toolbar, so buildings go up even while paused
+            .add_systems(Update, spawn_building.run_if(in_state(AppState::Game)))
             // On Exit State
             .add_systems(OnExit(AppState::Game), despawn_building);
     }
@@ -1,9 +1,7 @@
-use bevy::{prelude::*, sprite::MaterialMesh2dBundle, transform};
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
 
-use super::{
-    components::{Building, EventSpawnBuilding},
-    BUILDING_SIZE,
-};
+use super::components::{Building, EventSpawnBuilding};
+use crate::game::resources::GameAssets;
 
 pub fn despawn_building(mut commands: Commands, query: Query<Entity, With<Building>>) {
     for entity in query.iter() {
@@ -13,23 +11,15 @@ pub fn despawn_building(mut commands: Commands, query: Query<Entity, With<Buildi
 
 pub fn spawn_building(
     mut commands: Commands,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut meshes: ResMut<Assets<Mesh>>,
+    assets: Res<GameAssets>,
     mut events: EventReader<EventSpawnBuilding>,
 ) {
     for event in events.read() {
-        let position = event.position.clone();
+        let position = event.position;
         commands.spawn((
             MaterialMesh2dBundle {
-                mesh: meshes
-                    .add(
-                        shape::Cube {
-                            size: BUILDING_SIZE,
-                        }
-                        .into(),
-                    )
-                    .into(),
-                material: materials.add(ColorMaterial::from(Color::WHITE)),
+                mesh: assets.building_mesh.clone().into(),
+                material: assets.building_material.clone(),
                 transform: position,
                 ..Default::default()
             },
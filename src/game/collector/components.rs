@@ -5,7 +5,10 @@ use bevy::prelude::*;
 pub struct Collector {
     pub stash_pos: Transform,
     pub returning: bool,
-    pub carrying: Option<f32>,
+    /// Debri mass it can carry at once.
+    pub capacity: f32,
+    /// Score value of the debri being brought back.
+    pub carrying: Option<u32>,
 }
 
 #[derive(Event)]
@@ -3,14 +3,15 @@ mod systems;
 
 use self::components::CollectorSpawnEvent;
 
-use super::{debri::PHYISCS_TICK_RATE, SimulationState};
+use super::SimulationSet;
 use crate::AppState;
 
-use bevy::{prelude::*, time::common_conditions::on_timer};
-use instant::Duration;
+use bevy::prelude::*;
 use systems::*;
 
 pub const COLLECTOR_SIZE: f32 = 10.0;
+/// Debri mass a collector hauls per trip, heavier debri take several.
+pub const COLLECTOR_CAPACITY: f32 = 3.0;
 
 pub struct CollectorPlugin;
 
@@ -20,12 +21,12 @@ impl Plugin for CollectorPlugin {
             // Events
             .add_event::<CollectorSpawnEvent>()
             // Systems
+            // toolbar clicks are read every frame, so none are lost between
+            // physics ticks or while the simulation is paused
+            .add_systems(Update, spawn_collector.run_if(in_state(AppState::Game)))
             .add_systems(
                 FixedUpdate,
-                (collector_movement, spawn_collector)
-                    .run_if(on_timer(Duration::from_secs_f32(1. / PHYISCS_TICK_RATE)))
-                    .run_if(in_state(AppState::Game))
-                    .run_if(in_state(SimulationState::Running)),
+                collector_movement.in_set(SimulationSet::Collectors),
             )
             // On Exit State
             .add_systems(OnExit(AppState::Game), despawn_collector);
@@ -1,51 +1,49 @@
 use crate::game::{
-    components::Velocity,
+    components::{Bounded, Velocity},
     debri::{
-        components::{Collected, CollectedEvent},
+        components::{Bulk, Collected, CollectedEvent, Lifetime},
         resources::DebriUniverse,
     },
+    resources::{GameAssets, GameRng, WorldBounds},
 };
-use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
-use rand::prelude::ThreadRng;
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle, utils::HashSet};
 use rand::Rng;
 
 use crate::game::{debri::components::Collider, score::resources::Score};
+use crate::quadtree::layers::Layers;
 
 use super::{
     components::{Collector, CollectorSpawnEvent},
-    COLLECTOR_SIZE,
+    COLLECTOR_CAPACITY, COLLECTOR_SIZE,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn collector_movement(
-    mut query: Query<
-        (&mut Transform, &mut Collector, &Collider, &mut Velocity),
-        Without<Collected>,
-    >,
+    mut query: Query<(&mut Transform, &mut Collector, &mut Velocity), Without<Collected>>,
     mut score: ResMut<Score>,
-    collector_query: Query<&Collider, With<Collector>>,
     universe: Res<DebriUniverse>,
+    bounds: Res<WorldBounds>,
+    bulk_query: Query<(&Bulk, Option<&Lifetime>)>,
     time: Res<Time>,
+    mut rng: ResMut<GameRng>,
     mut events: EventWriter<CollectedEvent>,
 ) {
-    let mut rng = ThreadRng::default();
-    let exclude_ids = collector_query
-        .iter()
-        .filter_map(|collider| collider.id.clone())
-        .collect::<Vec<_>>();
+    let range = universe.vision * 4000.0;
+    // debri already loaded this tick, so two collectors never haul the same one
+    let mut claimed = HashSet::new();
 
-    for (mut transform, mut collector, collider, velocity) in query.iter_mut() {
+    for (mut transform, mut collector, velocity) in query.iter_mut() {
         if collector.returning {
-            let distance = transform
-                .translation
-                .distance(collector.stash_pos.translation);
+            let direction = bounds.offset(
+                &universe.bounds,
+                transform.translation,
+                collector.stash_pos.translation,
+            );
             // if reached stash
-            if distance < COLLECTOR_SIZE {
+            if direction.length() < COLLECTOR_SIZE {
                 collector.returning = false;
-                collector.carrying = None;
-
-                score.value += 1;
+                score.value += collector.carrying.take().unwrap_or(0);
             } else {
-                let direction = collector.stash_pos.translation - transform.translation;
                 let mut towards = if direction.length() > 0.0 {
                     direction.normalize()
                 } else {
@@ -59,18 +57,25 @@ pub fn collector_movement(
                 transform.translation.y += towards.y * time.delta_seconds() * velocity.value.y;
             }
         } else {
-            // -------------------- collision query --------------------
-            let query_region = collider
-                .into_region(transform.translation)
-                .with_margin((universe.vision * 4000.0) as i32);
-            let collisions = universe.graph.query(&query_region, &exclude_ids);
+            // -------------------- nearest query --------------------
+            let point = transform.translation.truncate();
+            let nearest = bounds
+                .images(&universe.bounds, point, range)
+                .into_iter()
+                .flat_map(|shift| {
+                    universe
+                        .graph
+                        .nearest(point + shift, 1, Layers::DEBRI, &|body| {
+                            !claimed.contains(&body.entity)
+                        })
+                })
+                .filter(|(_, distance)| *distance <= range)
+                .min_by(|(_, a), (_, b)| a.total_cmp(b));
 
             // move towards any debri in range
-            if let Some(nearest) = collisions
-                .iter()
-                .min_by_key(|body| (transform.translation - body.position).length_squared() as i32)
-            {
-                let direction = nearest.position - transform.translation;
+            if let Some((nearest, _)) = nearest {
+                let direction =
+                    bounds.offset(&universe.bounds, transform.translation, nearest.position);
                 let mut towards = if direction.length() > 0.0 {
                     direction.normalize()
                 } else {
@@ -84,14 +89,25 @@ pub fn collector_movement(
                 transform.translation.y += towards.y * time.delta_seconds() * velocity.value.y;
 
                 // collision with debri
-                let distance = transform.translation.distance(nearest.position);
+                let distance = bounds
+                    .offset(&universe.bounds, transform.translation, nearest.position)
+                    .length();
                 if distance < COLLECTOR_SIZE {
-                    events.send(CollectedEvent {
-                        entity: nearest.entity,
-                    });
+                    if let Ok((bulk, lifetime)) = bulk_query.get(nearest.entity) {
+                        claimed.insert(nearest.entity);
+                        // a load heavier than the capacity is chipped off over several trips
+                        let mut remaining = *bulk;
+                        let load = remaining.take(collector.capacity);
+                        events.send(CollectedEvent {
+                            entity: nearest.entity,
+                            mass: load.mass,
+                        });
 
-                    collector.returning = true;
-                    collector.carrying = Some(1.0);
+                        collector.returning = true;
+                        // aging debri are worth less
+                        let worth = lifetime.map_or(1.0, Lifetime::remaining);
+                        collector.carrying = Some((load.value as f32 * worth).round() as u32);
+                    }
                 } else {
                     collector.returning = false;
                     collector.carrying = None;
@@ -110,14 +126,13 @@ pub fn despawn_collector(mut commands: Commands, query: Query<Entity, With<Colle
 pub fn spawn_collector(
     mut events: EventReader<CollectorSpawnEvent>,
     mut commands: Commands,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut meshes: ResMut<Assets<Mesh>>,
+    assets: Res<GameAssets>,
 ) {
     for event in events.read() {
         commands.spawn((
             MaterialMesh2dBundle {
-                mesh: meshes.add(shape::Circle::new(COLLECTOR_SIZE).into()).into(),
-                material: materials.add(ColorMaterial::from(Color::RED)),
+                mesh: assets.collector_mesh.clone().into(),
+                material: assets.collector_material.clone(),
                 transform: Transform::from_xyz(
                     event.spawn_pos.translation.x,
                     event.spawn_pos.translation.y,
@@ -128,9 +143,11 @@ pub fn spawn_collector(
             Collector {
                 stash_pos: event.spawn_pos,
                 returning: false,
+                capacity: COLLECTOR_CAPACITY,
                 carrying: None,
             },
             Collider::new(COLLECTOR_SIZE),
+            Bounded,
             Velocity {
                 value: Vec3::new(200.0, 200.0, 0.0),
                 damping: 0.0,
@@ -139,3 +156,75 @@ pub fn spawn_collector(
         ));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::debri::{
+        components::{Debri, DebriKind, Flock},
+        resources::QuadBench,
+        systems::build_or_update_quadtree,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn two_collectors_never_load_the_same_debri() {
+        let mut world = World::new();
+        world.insert_resource(DebriUniverse::new(Vec2::ZERO, Vec2::splat(400.0)));
+        world.insert_resource(WorldBounds::default());
+        world.insert_resource(GameRng::new(1));
+        world.insert_resource(Score { value: 0 });
+        world.insert_resource(Time::<()>::default());
+        world.insert_resource(Events::<CollectedEvent>::default());
+        world.insert_resource(QuadBench::default());
+
+        let position = Vec3::new(200.0, 200.0, 0.0);
+        world.spawn((
+            Transform::from_translation(position),
+            Debri,
+            Velocity {
+                value: Vec3::ZERO,
+                damping: 0.0,
+                min_speed: 0.0,
+            },
+            Collider::new(2.0),
+            Flock(0),
+            DebriKind::Ore,
+            Bulk {
+                mass: 1.0,
+                value: 5,
+            },
+        ));
+        for _ in 0..2 {
+            world.spawn((
+                Transform::from_translation(position),
+                Collector {
+                    stash_pos: Transform::default(),
+                    returning: false,
+                    capacity: COLLECTOR_CAPACITY,
+                    carrying: None,
+                },
+                Velocity {
+                    value: Vec3::ZERO,
+                    damping: 0.0,
+                    min_speed: 0.0,
+                },
+            ));
+        }
+
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(1.0 / 90.0));
+        let mut schedule = Schedule::default();
+        schedule.add_systems((build_or_update_quadtree, collector_movement).chain());
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Events<CollectedEvent>>().len(), 1);
+        let mut collectors = world.query::<&Collector>();
+        let loaded = collectors
+            .iter(&world)
+            .filter(|collector| collector.carrying.is_some())
+            .count();
+        assert_eq!(loaded, 1);
+    }
+}
@@ -16,3 +16,39 @@ pub struct OrbitCenter {
     pub x: f32,
     pub y: f32,
 }
+/// Kept to the `WorldBounds` by `keep_in_bounds`. Debri keep to them in
+/// their own physics tick instead.
+#[derive(Component)]
+pub struct Bounded;
+/// Pulls debri in with an acceleration of `mass / distance^falloff`, so a
+/// `falloff` of 2 is Newtonian gravity.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct GravityWell {
+    pub mass: f32,
+    /// Closer than this the pull stops growing, so debri passing through the
+    /// middle are not flung away.
+    pub radius: f32,
+    pub falloff: f32,
+}
+
+impl Default for GravityWell {
+    fn default() -> Self {
+        Self {
+            mass: 1.5e6,
+            radius: 40.0,
+            falloff: 2.0,
+        }
+    }
+}
+
+impl GravityWell {
+    /// Acceleration of a body `offset` away from the well towards it.
+    pub fn acceleration(&self, offset: Vec3) -> Vec3 {
+        let distance = offset.length();
+        if distance <= 0.0 {
+            return Vec3::ZERO;
+        }
+        let strength = self.mass / distance.max(self.radius).powf(self.falloff);
+        -offset / distance * strength
+    }
+}
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use crate::game::components::Position;
-use crate::quadtree::{coord::Coord, region::Region, slot_map::SlotId};
+use crate::quadtree::{region::Region, slot_map::SlotId};
 
 #[derive(Component, Clone)]
 
@@ -32,18 +32,93 @@ impl Collider {
         }
     }
     pub fn into_region(&self, origin: Vec3) -> Region {
-        let min =
-            Coord::from_f32(origin.x, origin.y) - Coord::from_f32(self.radius, self.radius) / 2;
-        let max =
-            Coord::from_f32(origin.x, origin.y) + Coord::from_f32(self.radius, self.radius) / 2;
+        Region::from_center(origin.truncate(), Vec2::splat(self.radius))
+    }
+}
+
+/// Which `FlockRules` in `FlockParams` a debri steers by.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Flock(pub usize);
+
+/// What a debri is made of. Its `DebriKindRules` in `DebriKinds` set how it
+/// looks, how heavy it is and what it is worth.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DebriKind {
+    #[default]
+    Scrap,
+    Ore,
+    Crystal,
+    Volatile,
+}
+
+impl DebriKind {
+    pub const ALL: [DebriKind; 4] = [
+        DebriKind::Scrap,
+        DebriKind::Ore,
+        DebriKind::Crystal,
+        DebriKind::Volatile,
+    ];
+}
+
+/// Mass and score value of one debri. Merged debri add theirs up.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Bulk {
+    pub mass: f32,
+    pub value: u32,
+}
+
+impl Bulk {
+    /// Splits off up to `mass`, with a matching share of the value. Taking
+    /// everything that is left leaves the bulk empty.
+    pub fn take(&mut self, mass: f32) -> Bulk {
+        if mass >= self.mass {
+            return std::mem::replace(
+                self,
+                Bulk {
+                    mass: 0.0,
+                    value: 0,
+                },
+            );
+        }
+        let value = ((self.value as f32 * mass / self.mass).round() as u32).min(self.value);
+        self.mass -= mass;
+        self.value -= value;
+        Bulk { mass, value }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mass <= 0.0
+    }
+}
+
+/// Debri with a lifetime fade out, and are worth less, as they age. They
+/// are despawned once `age` reaches `span`. Both are in seconds.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Lifetime {
+    pub age: f32,
+    pub span: f32,
+}
+
+impl Lifetime {
+    pub fn new(span: f32) -> Self {
+        Self { age: 0.0, span }
+    }
+
+    /// Share of the lifetime still ahead, from 1 when new down to 0.
+    pub fn remaining(&self) -> f32 {
+        (1.0 - self.age / self.span).clamp(0.0, 1.0)
+    }
 
-        Region::new(min, max)
+    pub fn is_over(&self) -> bool {
+        self.age >= self.span
     }
 }
 
 #[derive(Debug)]
 pub struct Body {
     pub entity: Entity,
+    pub flock: Flock,
+    pub kind: DebriKind,
     pub position: Vec3,
     pub velocity: Vec3,
 }
@@ -54,4 +129,6 @@ pub struct QuadNodeRect;
 #[derive(Event)]
 pub struct CollectedEvent {
     pub entity: Entity,
+    /// How much of the debri's `Bulk` was hauled off.
+    pub mass: f32,
 }
@@ -1,45 +1,83 @@
 pub mod components;
 mod init;
-mod systems;
+pub mod systems;
 
 use init::*;
-use std::time::Duration;
+use std::path::PathBuf;
 pub mod resources;
+pub mod snapshot;
 
 use resources::*;
 use systems::*;
 
-use super::SimulationState;
+use super::SimulationSet;
 use crate::AppState;
-use bevy::time::common_conditions::on_timer;
 
 use bevy::prelude::*;
 
 pub const DEBRI_SIZE: f32 = 8.0;
 pub const PHYISCS_TICK_RATE: f32 = 90.;
+pub const SPATIAL_CELL_SIZE: f32 = 32.0;
+/// Speed debri are knocked off the target at. The default `GravityWell` holds
+/// a launch along its core's edge at this speed in an orbit reaching out to
+/// about three times the core's radius.
+pub const DEBRI_LAUNCH_SPEED: f32 = 240.0;
+/// Outward part of the launch for each unit along the core's surface.
+pub const DEBRI_LAUNCH_TILT: f32 = 0.25;
+/// Set to a file path to dump `QuadBench` as CSV when the game exits.
+pub const BENCH_CSV_ENV: &str = "QUAD_BENCH_CSV";
+/// Alpha steps a fading debri is drawn with, each one a shared material.
+pub const FADE_LEVELS: usize = 32;
+/// Where `F5` writes spatial index snapshots.
+pub const SNAPSHOT_DIR: &str = "snapshots";
 
 pub struct DebriPlugin;
 
 impl Plugin for DebriPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(QuadBench::default())
+        app
+            // Resources
+            .init_resource::<FlockParams>()
+            .init_resource::<DebriKinds>()
+            .init_resource::<DebriMerging>()
+            .init_resource::<DebriDecay>()
+            .init_resource::<PhysicsTickRate>()
+            .insert_resource(QuadBench {
+                csv_path: std::env::var_os(BENCH_CSV_ENV).map(PathBuf::from),
+                ..default()
+            })
             .add_event::<components::SpawnDebri>()
             .add_event::<components::CollectedEvent>()
             // Systems
             .add_systems(Startup, insert_debri_universe)
             .add_systems(
                 FixedUpdate,
-                (spawn_debri, handle_debri_collected_event, count_debri)
-                    .run_if(in_state(AppState::Game))
-                    .run_if(in_state(SimulationState::Running)),
+                (
+                    spawn_debri,
+                    build_or_update_quadtree,
+                    merge_debri,
+                    update_debri,
+                    move_system,
+                    keep_debri_in_bounds,
+                )
+                    .chain()
+                    .in_set(SimulationSet::Debri),
+            )
+            .add_systems(
+                FixedUpdate,
+                (handle_debri_collected_event, age_debri, count_debri)
+                    .chain()
+                    .in_set(SimulationSet::Cleanup),
+            )
+            .add_systems(
+                Update,
+                sync_fixed_timestep.run_if(resource_changed::<PhysicsTickRate>()),
             )
             .add_systems(
                 Update,
-                (build_or_update_quadtree, update_debri, move_system)
-                    .run_if(on_timer(Duration::from_secs_f32(1. / PHYISCS_TICK_RATE)))
-                    .run_if(in_state(AppState::Game))
-                    .run_if(in_state(SimulationState::Running)),
+                (switch_spatial_backend, export_snapshot).run_if(in_state(AppState::Game)),
             )
+            .add_systems(Last, write_bench_csv)
             // On Exit State
             .add_systems(OnExit(AppState::Game), despawn_debri);
     }
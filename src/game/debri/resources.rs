@@ -1,41 +1,397 @@
 use bevy::prelude::*;
-use crate::quadtree::{tree::QuadTree, coord::Coord, region::Region};
-use super::components::*;
+use instant::Duration;
+use rand::Rng;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
 
+use super::{components::*, DEBRI_SIZE, PHYISCS_TICK_RATE, SPATIAL_CELL_SIZE};
+use crate::quadtree::{config::QuadTreeConfig, region::Region, tree::QuadTree};
+use crate::spatial::{
+    grid::Grid, spatial_hash::SpatialHash, IndexStats, SpatialBackend, SpatialIndex,
+};
 
 #[derive(Resource)]
 pub struct DebriUniverse {
-    pub graph: QuadTree<Body>,
-    pub speration: f32,
-    pub cohesion: f32,
-    pub alignment: f32,
+    pub graph: Box<dyn SpatialIndex<Body> + Send + Sync>,
+    /// Edges of the world. The index may grow past them to fit stray debri.
+    pub bounds: Region,
+    pub backend: SpatialBackend,
     pub vision: f32,
     pub speed: f32,
     pub show_graph: bool,
-    pub debri_count : u32,
-    pub mouse_used_by_egui : bool,
+    pub debri_count: u32,
+    /// `Velocity` damping and min speed of every debri.
+    pub damping: f32,
+    pub min_speed: f32,
 }
 
+/// How many times per second the physics tick runs.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct PhysicsTickRate(pub f32);
+
+impl PhysicsTickRate {
+    pub fn period(&self) -> Duration {
+        Duration::from_secs_f32(1.0 / self.0.max(1.0))
+    }
+}
+
+impl Default for PhysicsTickRate {
+    fn default() -> Self {
+        Self(PHYISCS_TICK_RATE)
+    }
+}
+
+/// Steering of one flock. Weights scale the three boid rules, forces are in
+/// units per second squared and speeds in units per second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlockRules {
+    pub separation: f32,
+    pub cohesion: f32,
+    pub alignment: f32,
+    /// How far past its own collider a boid sees its neighbours.
+    pub vision: f32,
+    /// Neighbours closer than this push the boid away.
+    pub personal_space: f32,
+    pub max_force: f32,
+    pub max_speed: f32,
+    /// Relative chance a new debri joins this flock.
+    pub spawn_weight: f32,
+}
+
+impl FlockRules {
+    /// Radius within which a boid of the given size sees its neighbours.
+    pub fn vision_radius(&self, radius: f32) -> f32 {
+        radius + self.vision
+    }
+}
+
+impl Default for FlockRules {
+    fn default() -> Self {
+        Self {
+            separation: 1.5,
+            cohesion: 1.0,
+            alignment: 1.0,
+            vision: 10.0,
+            personal_space: DEBRI_SIZE,
+            max_force: 200.0,
+            max_speed: 240.0,
+            spawn_weight: 1.0,
+        }
+    }
+}
+
+/// Rules of every flock, indexed by `Flock`.
+#[derive(Resource, Debug, Clone)]
+pub struct FlockParams {
+    pub flocks: Vec<FlockRules>,
+}
+
+impl FlockParams {
+    /// Rules of `flock`, falling back to the first flock for unknown ids.
+    pub fn rules(&self, flock: Flock) -> &FlockRules {
+        self.flocks.get(flock.0).unwrap_or(&self.flocks[0])
+    }
+
+    /// Picks a flock for a new debri according to the spawn weights.
+    pub fn pick<R: Rng>(&self, rng: &mut R) -> Flock {
+        let total: f32 = self.flocks.iter().map(|rules| rules.spawn_weight).sum();
+        let mut roll = rng.gen_range(0.0..total.max(f32::EPSILON));
+        for (index, rules) in self.flocks.iter().enumerate() {
+            if roll < rules.spawn_weight {
+                return Flock(index);
+            }
+            roll -= rules.spawn_weight;
+        }
+        Flock(0)
+    }
+}
+
+impl Default for FlockParams {
+    /// A tight swarm and a looser, faster drift.
+    fn default() -> Self {
+        Self {
+            flocks: vec![
+                FlockRules::default(),
+                FlockRules {
+                    separation: 2.0,
+                    cohesion: 0.4,
+                    alignment: 1.5,
+                    vision: 20.0,
+                    max_speed: 300.0,
+                    spawn_weight: 0.5,
+                    ..default()
+                },
+            ],
+        }
+    }
+}
+
+/// Look and worth of one `DebriKind`.
+#[derive(Debug, Clone, Copy)]
+pub struct DebriKindRules {
+    pub color: Color,
+    /// Side of the debri's quad, its collider is half as wide.
+    pub size: f32,
+    /// Divides the flocking forces, heavy debri turn slowly.
+    pub mass: f32,
+    /// Score a collector earns for delivering it.
+    pub value: u32,
+    /// Relative chance a new debri is of this kind.
+    pub spawn_weight: f32,
+}
+
+impl DebriKindRules {
+    /// Collider radius of a debri of this kind weighing `mass`. The area
+    /// grows with the mass.
+    pub fn radius(&self, mass: f32) -> f32 {
+        self.size / 2.0 * (mass / self.mass).max(0.0).sqrt()
+    }
+
+    pub fn bulk(&self) -> Bulk {
+        Bulk {
+            mass: self.mass,
+            value: self.value,
+        }
+    }
+}
+
+/// Rules of every `DebriKind`, in `DebriKind::ALL` order.
+#[derive(Resource, Debug, Clone)]
+pub struct DebriKinds {
+    pub kinds: [DebriKindRules; 4],
+}
+
+impl DebriKinds {
+    pub fn rules(&self, kind: DebriKind) -> &DebriKindRules {
+        &self.kinds[kind as usize]
+    }
+
+    /// Picks the kind of a new debri according to the spawn weights.
+    pub fn pick<R: Rng>(&self, rng: &mut R) -> DebriKind {
+        let total: f32 = self.kinds.iter().map(|rules| rules.spawn_weight).sum();
+        let mut roll = rng.gen_range(0.0..total.max(f32::EPSILON));
+        for (kind, rules) in DebriKind::ALL.into_iter().zip(self.kinds.iter()) {
+            if roll < rules.spawn_weight {
+                return kind;
+            }
+            roll -= rules.spawn_weight;
+        }
+        DebriKind::default()
+    }
+}
+
+impl Default for DebriKinds {
+    /// Mostly scrap, the rarer the kind the more it is worth.
+    fn default() -> Self {
+        Self {
+            kinds: [
+                DebriKindRules {
+                    color: Color::rgb(2.0, 2.0, 0.0),
+                    size: DEBRI_SIZE,
+                    mass: 1.0,
+                    value: 1,
+                    spawn_weight: 0.6,
+                },
+                DebriKindRules {
+                    color: Color::rgb(1.6, 0.8, 0.3),
+                    size: DEBRI_SIZE * 1.25,
+                    mass: 2.5,
+                    value: 3,
+                    spawn_weight: 0.25,
+                },
+                DebriKindRules {
+                    color: Color::rgb(0.4, 1.8, 2.2),
+                    size: DEBRI_SIZE * 0.75,
+                    mass: 0.6,
+                    value: 8,
+                    spawn_weight: 0.1,
+                },
+                DebriKindRules {
+                    color: Color::rgb(2.4, 0.4, 0.6),
+                    size: DEBRI_SIZE * 0.875,
+                    mass: 0.8,
+                    value: 5,
+                    spawn_weight: 0.05,
+                },
+            ],
+        }
+    }
+}
+
+/// Touching debri fuse into one body. Merging is off while `chance` is zero.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct DebriMerging {
+    /// Chance per physics tick that two touching debri merge.
+    pub chance: f32,
+    /// Debri never merge into something heavier than this.
+    pub max_mass: f32,
+}
+
+impl Default for DebriMerging {
+    fn default() -> Self {
+        Self {
+            chance: 0.0,
+            max_mass: 12.0,
+        }
+    }
+}
+
+/// How debri leave the game other than by being collected.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct DebriDecay {
+    /// Seconds a new debri lives, zero lets it live forever.
+    pub lifetime: f32,
+}
+
+/// Number of physics ticks the rolling averages and p95 are taken over.
+pub const BENCH_WINDOW: usize = 120;
+
+#[derive(Default)]
+pub struct RollingTimings {
+    samples: VecDeque<u128>,
+}
+
+impl RollingTimings {
+    pub fn push(&mut self, micros: u128) {
+        if self.samples.len() == BENCH_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(micros);
+    }
+
+    pub fn average(&self) -> u128 {
+        if self.samples.is_empty() {
+            return 0;
+        }
+        self.samples.iter().sum::<u128>() / self.samples.len() as u128
+    }
+
+    pub fn p95(&self) -> u128 {
+        let mut sorted: Vec<u128> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        match sorted.len() {
+            0 => 0,
+            len => sorted[((len - 1) as f32 * 0.95).round() as usize],
+        }
+    }
+}
+
+pub struct BenchRecord {
+    pub tick: u64,
+    pub build_time: u128,
+    pub query_time: u128,
+    pub values: usize,
+    pub stats: IndexStats,
+}
+
+/// Timings of the physics tick. All times are in microseconds.
 #[derive(Resource, Default)]
 pub struct QuadBench {
     pub avarage_query_time: u128,
     pub avarage_build_time: u128,
+    pub p95_query_time: u128,
+    pub p95_build_time: u128,
+    pub query_times: RollingTimings,
+    pub build_times: RollingTimings,
+    pub stats: IndexStats,
+    /// Physics ticks run so far, counting the one in progress.
+    pub tick: u64,
+    /// One record per physics tick, written out by `write_csv`. Only kept
+    /// when there is a `csv_path` to write it to.
+    pub history: Vec<BenchRecord>,
+    /// Where to dump `history` when the app exits, if anywhere.
+    pub csv_path: Option<PathBuf>,
+    pub show_overlay: bool,
+}
+
+impl QuadBench {
+    pub fn record_build(&mut self, time: Duration, values: usize, stats: IndexStats) {
+        self.build_times.push(time.as_micros());
+        self.avarage_build_time = self.build_times.average();
+        self.p95_build_time = self.build_times.p95();
+        self.stats = stats;
+        self.tick += 1;
+
+        if self.csv_path.is_some() {
+            // the query time is filled in by `record_query` later this tick
+            self.history.push(BenchRecord {
+                tick: self.tick,
+                build_time: time.as_micros(),
+                query_time: 0,
+                values,
+                stats,
+            });
+        }
+    }
+
+    pub fn record_query(&mut self, time: Duration) {
+        self.query_times.push(time.as_micros());
+        self.avarage_query_time = self.query_times.average();
+        self.p95_query_time = self.query_times.p95();
+
+        let tick = self.tick;
+        if let Some(record) = self.history.last_mut().filter(|record| record.tick == tick) {
+            record.query_time = time.as_micros();
+        }
+    }
+
+    pub fn write_csv(&self, path: &Path) -> io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(
+            file,
+            "tick,build_us,query_us,values,nodes,leaves,max_depth,values_per_leaf"
+        )?;
+        for record in &self.history {
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{},{:.2}",
+                record.tick,
+                record.build_time,
+                record.query_time,
+                record.values,
+                record.stats.nodes,
+                record.stats.leaves,
+                record.stats.max_depth,
+                record.stats.values_per_leaf(),
+            )?;
+        }
+        file.flush()
+    }
 }
 
 impl DebriUniverse {
     pub fn new(min: Vec2, max: Vec2) -> Self {
-        let _min = Coord::from_f32(min.x, min.y);
-        let _max = Coord::from_f32(max.x, max.y);
+        let bounds = Region::new(min, max);
         Self {
-            graph: QuadTree::new(Region::new(_min, _max)),
-            speration: 0.1,
-            cohesion: 0.1,
+            graph: build_graph(SpatialBackend::default(), bounds),
+            bounds,
+            backend: SpatialBackend::default(),
             speed: 1.0,
-            vision : 1.0,
-            alignment: 0.1,
-            debri_count : 0,
-            show_graph: true,
-            mouse_used_by_egui : false,
+            vision: 1.0,
+            debri_count: 0,
+            show_graph: false,
+            damping: 50.0,
+            min_speed: 50.0,
         }
     }
+
+    /// Swaps the spatial index for an empty one of the given kind. Every
+    /// `Collider.id` handed out by the old index must be dropped by the caller.
+    pub fn set_backend(&mut self, backend: SpatialBackend) {
+        self.graph = build_graph(backend, self.bounds);
+        self.backend = backend;
+    }
+}
+
+fn build_graph(
+    backend: SpatialBackend,
+    region: Region,
+) -> Box<dyn SpatialIndex<Body> + Send + Sync> {
+    match backend {
+        SpatialBackend::QuadTree => Box::new(QuadTree::new(region, QuadTreeConfig::default())),
+        SpatialBackend::Grid => Box::new(Grid::new(region, SPATIAL_CELL_SIZE)),
+        SpatialBackend::SpatialHash => Box::new(SpatialHash::new(region, SPATIAL_CELL_SIZE)),
+    }
 }
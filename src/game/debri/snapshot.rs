@@ -0,0 +1,240 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+
+use super::{
+    components::{DebriKind, Flock},
+    resources::DebriUniverse,
+};
+use crate::quadtree::region::Region;
+use crate::spatial::SpatialBackend;
+
+pub struct NodeSnapshot {
+    pub region: Region,
+    pub depth: usize,
+    pub values: usize,
+}
+
+pub struct BodySnapshot {
+    pub entity: Entity,
+    pub flock: Flock,
+    pub kind: DebriKind,
+    pub position: Vec3,
+    pub velocity: Vec3,
+}
+
+/// The spatial index and the bodies in it as of the last physics tick.
+pub struct Snapshot {
+    pub tick: u64,
+    pub backend: SpatialBackend,
+    pub bounds: Region,
+    pub nodes: Vec<NodeSnapshot>,
+    pub bodies: Vec<BodySnapshot>,
+}
+
+impl Snapshot {
+    pub fn capture(universe: &DebriUniverse, tick: u64) -> Self {
+        let mut nodes = Vec::new();
+        universe.graph.for_each_leaf(&mut |region, depth, values| {
+            nodes.push(NodeSnapshot {
+                region: *region,
+                depth,
+                values,
+            })
+        });
+
+        let bounds = *universe.graph.size();
+        let mut bodies = Vec::new();
+        universe.graph.for_each_in(&bounds, &mut |_, body| {
+            bodies.push(BodySnapshot {
+                entity: body.entity,
+                flock: body.flock,
+                kind: body.kind,
+                position: body.position,
+                velocity: body.velocity,
+            })
+        });
+
+        Self {
+            tick,
+            backend: universe.backend,
+            bounds,
+            nodes,
+            bodies,
+        }
+    }
+
+    pub fn write_json<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "{{")?;
+        writeln!(out, "  \"tick\": {},", self.tick)?;
+        writeln!(out, "  \"backend\": \"{:?}\",", self.backend)?;
+        writeln!(
+            out,
+            "  \"bounds\": {{ \"min\": {}, \"max\": {} }},",
+            json_vec(self.bounds.min),
+            json_vec(self.bounds.max)
+        )?;
+
+        writeln!(out, "  \"nodes\": [")?;
+        for (index, node) in self.nodes.iter().enumerate() {
+            writeln!(
+                out,
+                "    {{ \"min\": {}, \"max\": {}, \"depth\": {}, \"values\": {} }}{}",
+                json_vec(node.region.min),
+                json_vec(node.region.max),
+                node.depth,
+                node.values,
+                separator(index, self.nodes.len())
+            )?;
+        }
+        writeln!(out, "  ],")?;
+
+        writeln!(out, "  \"bodies\": [")?;
+        for (index, body) in self.bodies.iter().enumerate() {
+            writeln!(
+                out,
+                "    {{ \"entity\": {}, \"flock\": {}, \"kind\": \"{:?}\", \"position\": {}, \"velocity\": {} }}{}",
+                body.entity.to_bits(),
+                body.flock.0,
+                body.kind,
+                json_vec(body.position.truncate()),
+                json_vec(body.velocity.truncate()),
+                separator(index, self.bodies.len())
+            )?;
+        }
+        writeln!(out, "  ]")?;
+        writeln!(out, "}}")?;
+        out.flush()
+    }
+
+    /// Draws the leaves shaded by how full they are, and every body with a
+    /// line along its velocity. World y points up, so the image is flipped.
+    pub fn write_svg<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let size = self.bounds.size();
+        let fullest = self
+            .nodes
+            .iter()
+            .map(|node| node.values)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+            self.bounds.min.x, -self.bounds.max.y, size.x, size.y
+        )?;
+        writeln!(out, "<g transform=\"scale(1,-1)\">")?;
+        writeln!(
+            out,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"black\"/>",
+            self.bounds.min.x, self.bounds.min.y, size.x, size.y
+        )?;
+
+        for node in self.nodes.iter() {
+            let size = node.region.size();
+            writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"red\" \
+                 fill-opacity=\"{:.3}\" stroke=\"white\" stroke-width=\"0.5\"/>",
+                node.region.min.x,
+                node.region.min.y,
+                size.x,
+                size.y,
+                node.values as f32 / fullest as f32 * 0.6
+            )?;
+        }
+
+        for body in self.bodies.iter().filter(|body| body.position.is_finite()) {
+            let heading = body.position + body.velocity.normalize_or_zero() * 6.0;
+            writeln!(
+                out,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"2\" fill=\"yellow\"/>\
+                 <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"yellow\" stroke-width=\"0.5\"/>",
+                body.position.x,
+                body.position.y,
+                body.position.x,
+                body.position.y,
+                heading.x,
+                heading.y
+            )?;
+        }
+
+        writeln!(out, "</g>")?;
+        writeln!(out, "</svg>")?;
+        out.flush()
+    }
+
+    /// Writes `tick-<tick>.json` and `tick-<tick>.svg` into `dir` and returns
+    /// the path of the JSON file.
+    pub fn save(&self, dir: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let json = dir.join(format!("tick-{}.json", self.tick));
+        self.write_json(BufWriter::new(File::create(&json)?))?;
+        let svg = dir.join(format!("tick-{}.svg", self.tick));
+        self.write_svg(BufWriter::new(File::create(svg)?))?;
+        Ok(json)
+    }
+}
+
+// JSON has no NaN or infinity, a body that lost its velocity shows up as null.
+fn json_number(value: f32) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+fn json_vec(value: Vec2) -> String {
+    format!("[{}, {}]", json_number(value.x), json_number(value.y))
+}
+
+fn separator(index: usize, len: usize) -> &'static str {
+    if index + 1 < len {
+        ","
+    } else {
+        ""
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::debri::components::Body;
+
+    #[test]
+    fn snapshot_lists_every_leaf_and_body() {
+        let mut universe = DebriUniverse::new(Vec2::ZERO, Vec2::splat(100.0));
+        for index in 0..40 {
+            let position = Vec3::new((index * 2) as f32 + 0.5, (index * 2) as f32 + 0.5, 0.0);
+            let body = Body {
+                entity: Entity::from_raw(index),
+                flock: Flock(0),
+                kind: DebriKind::default(),
+                position,
+                velocity: Vec3::X,
+            };
+            let region = Region::from_center(position.truncate(), Vec2::ONE);
+            universe.graph.insert(region, body).unwrap();
+        }
+
+        let snapshot = Snapshot::capture(&universe, 7);
+        assert_eq!(snapshot.bodies.len(), 40);
+        assert_eq!(snapshot.nodes.len(), universe.graph.stats().leaves);
+
+        let mut json = Vec::new();
+        snapshot.write_json(&mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert!(json.contains("\"tick\": 7"));
+        assert_eq!(json.matches("\"position\"").count(), 40);
+
+        let mut svg = Vec::new();
+        snapshot.write_svg(&mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert_eq!(svg.matches("<circle").count(), 40);
+        assert_eq!(svg.matches("<rect").count(), snapshot.nodes.len() + 1);
+    }
+}
@@ -1,120 +1,261 @@
-use crate::game::{collector::components::Collector, components::Velocity};
-use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use crate::game::{
+    collector::components::Collector,
+    components::{GravityWell, Velocity},
+    resources::{BoundsHit, GameAssets, GameRng, WorldBounds},
+};
+use crate::quadtree::layers::Layers;
+use bevy::{app::AppExit, prelude::*, sprite::MaterialMesh2dBundle, utils::HashSet};
+use instant::Instant;
 use rand::Rng;
+use rayon::prelude::*;
 use std::f32::consts::PI;
+use std::path::Path;
 
 use super::{
-    components::{Body, Collected, CollectedEvent, Collider, Debri, SpawnDebri},
-    DebriUniverse, DEBRI_SIZE,
+    components::{
+        Body, Bulk, Collected, CollectedEvent, Collider, Debri, DebriKind, Flock, Lifetime,
+        SpawnDebri,
+    },
+    resources::{
+        DebriDecay, DebriKindRules, DebriKinds, DebriMerging, FlockParams, FlockRules,
+        PhysicsTickRate, QuadBench,
+    },
+    snapshot::Snapshot,
+    DebriUniverse, DEBRI_LAUNCH_SPEED, DEBRI_LAUNCH_TILT, SNAPSHOT_DIR,
 };
 
+/// Keeps the `FixedUpdate` timestep in step with `PhysicsTickRate`.
+pub fn sync_fixed_timestep(rate: Res<PhysicsTickRate>, mut time: ResMut<Time<Fixed>>) {
+    time.set_timestep(rate.period());
+}
+
+/// What the spatial index stores about each debri.
+type Indexed = (
+    Entity,
+    &'static Transform,
+    &'static mut Collider,
+    &'static Velocity,
+    &'static Flock,
+    &'static DebriKind,
+);
+
+/// Debri entities, leaving out collectors that share their components.
+type DebriOnly = (With<Debri>, Without<Collector>);
+
 pub fn build_or_update_quadtree(
-    mut query: Query<(Entity, &Transform, &mut Collider, &Velocity), Without<Collector>>,
+    mut query: Query<Indexed, Without<Collector>>,
     mut universe: ResMut<DebriUniverse>,
+    mut bench: ResMut<QuadBench>,
 ) {
-    universe.graph.clear();
+    let start = Instant::now();
     query
         .iter_mut()
-        .for_each(|(entity, transform, mut collider, velocity)| {
-            let id = Some(universe.graph.insert(
-                collider.into_region(transform.translation),
-                Body {
-                    entity,
-                    position: transform.translation,
-                    velocity: velocity.value,
-                },
-            ));
-            collider.id = id;
+        .for_each(|(entity, transform, mut collider, velocity, flock, kind)| {
+            let region = collider.into_region(transform.translation);
+            let body = Body {
+                entity,
+                flock: *flock,
+                kind: *kind,
+                position: transform.translation,
+                velocity: velocity.value,
+            };
+            collider.id = match &collider.id {
+                Some(id) if universe.graph.contains(id) => {
+                    match universe.graph.update(id, region, body) {
+                        Ok(()) => Some(id.clone()),
+                        Err(_) => {
+                            universe.graph.remove(id);
+                            None
+                        }
+                    }
+                }
+                _ => universe
+                    .graph
+                    .insert_with_layers(region, Layers::DEBRI, body)
+                    .ok(),
+            };
         });
+    bench.record_build(
+        start.elapsed(),
+        universe.graph.value_count(),
+        universe.graph.stats(),
+    );
 }
 
+/// A boid and the state it steers.
+type Steered = (
+    Entity,
+    &'static Transform,
+    &'static mut Collider,
+    &'static mut Velocity,
+    &'static Flock,
+    &'static Bulk,
+);
+
+#[allow(clippy::too_many_arguments)]
 pub fn update_debri(
-    mut query: Query<(Entity, &Transform, &mut Collider, &mut Velocity), Without<Collector>>,
+    mut query: Query<Steered, Without<Collector>>,
+    well_query: Query<(&Transform, &GravityWell)>,
     universe: Res<DebriUniverse>,
+    params: Res<FlockParams>,
+    bounds: Res<WorldBounds>,
+    mut rng: ResMut<GameRng>,
+    mut bench: ResMut<QuadBench>,
     time: Res<Time>,
 ) {
-    query
-        .iter_mut()
-        .for_each(|(_entity, transform, mut collider, mut velocity)| {
-            // if collected
-            let x = transform.translation.x as i32;
-            let y = transform.translation.y as i32;
-            let win = universe.graph.size();
-
-            // -------------------- collision query --------------------
-            let query_region = collider
-                .into_region(transform.translation)
-                .with_margin((universe.vision * 10.0) as i32);
-            let exclude = match &collider.id {
-                Some(id) => vec![id.clone()],
-                None => vec![],
-            };
+    let start = Instant::now();
 
-            let collisions = universe.graph.query(&query_region, &exclude);
-            collider.nearby = collisions.len();
-
-            let (mass_center, aligment, separtion) = collisions.iter().fold(
-                (Vec3::ZERO, Vec3::ZERO, Vec3::ZERO),
-                |(mcen, alg, sep), body| {
-                    (
-                        mcen + body.position.normalize(),
-                        alg + body.velocity.normalize(),
-                        sep + (transform.translation - body.position).normalize(),
-                    )
-                },
-            );
+    // read phase: every boid only looks at the index, so they steer in parallel.
+    // The random nudges are drawn up front, in query order, to stay reproducible.
+    let boids: Vec<_> = query
+        .iter()
+        .map(
+            |(entity, transform, collider, velocity, flock, bulk)| Boid {
+                entity,
+                flock: *flock,
+                position: transform.translation,
+                radius: collider.radius,
+                mass: bulk.mass.max(f32::EPSILON),
+                velocity: velocity.clone(),
+                jitter: random_jitter(&mut *rng),
+            },
+        )
+        .collect();
+    let wells: Vec<_> = well_query
+        .iter()
+        .map(|(transform, well)| (transform.translation, *well))
+        .collect();
+    let delta_time = universe.speed * time.delta_seconds();
+    let steered: Vec<_> = boids
+        .par_iter()
+        .map(|boid| {
+            let rules = params.rules(boid.flock);
+            let (nearby, new_velocity) = steer(&universe, &bounds, &wells, rules, boid, delta_time);
+            (boid.entity, nearby, new_velocity)
+        })
+        .collect();
 
-            let mut direction = velocity.value.normalize();
+    // write phase
+    for (entity, nearby, new_velocity) in steered {
+        if let Ok((_, _, mut collider, mut velocity, _, _)) = query.get_mut(entity) {
+            collider.nearby = nearby;
+            velocity.value = new_velocity;
+        }
+    }
+    bench.record_query(start.elapsed());
+}
 
-            // -------------------- Cohesion --------------------
-            if mass_center.length() > 0.0 {
-                direction += (mass_center.normalize() - transform.translation.normalize())
-                    .normalize()
-                    * universe.cohesion;
-            }
+// Copy of a debri taken before steering, so the steering can run in parallel.
+struct Boid {
+    entity: Entity,
+    flock: Flock,
+    position: Vec3,
+    radius: f32,
+    mass: f32,
+    velocity: Velocity,
+    jitter: Vec3,
+}
 
-            // -------------------- Alignment --------------------
-            if aligment.length() > 0.0 {
-                direction += aligment.normalize() * universe.alignment;
-            }
+// One debri in a hundred gets a small random push each tick.
+fn random_jitter<R: Rng>(rng: &mut R) -> Vec3 {
+    if rng.gen_range(0..100) < 1 {
+        Vec3::new(rng.gen_range(-10.0..10.0), rng.gen_range(-10.0..10.0), 0.0)
+    } else {
+        Vec3::ZERO
+    }
+}
 
-            // -------------------- Separation --------------------
-            if separtion.length() > 0.0 {
-                direction += separtion.normalize() * universe.speration;
-            }
+// Computes the new velocity of one boid from its neighbours and the gravity
+// wells, returning how many neighbours it saw alongside. Cohesion and
+// alignment only look at the boid's own flock, separation keeps it clear of
+// every debri.
+fn steer(
+    universe: &DebriUniverse,
+    bounds: &WorldBounds,
+    wells: &[(Vec3, GravityWell)],
+    rules: &FlockRules,
+    boid: &Boid,
+    delta_time: f32,
+) -> (usize, Vec3) {
+    let velocity = boid.velocity.value;
 
-            let mut new_velocity = direction.normalize() * velocity.value.length();
+    // -------------------- collision query --------------------
+    let mut nearby = 0;
+    let mut flockmates = 0;
+    let (mut center, mut heading, mut push) = (Vec3::ZERO, Vec3::ZERO, Vec3::ZERO);
+    let vision = rules.vision_radius(boid.radius);
+    for shift in bounds.images(&universe.bounds, boid.position.truncate(), vision) {
+        universe.graph.for_each_in_circle(
+            boid.position.truncate() + shift,
+            vision,
+            &mut |_, body| {
+                if body.entity == boid.entity {
+                    return;
+                }
+                nearby += 1;
 
-            // -------------------- World Border --------------------
-            let margin: i32 = 20;
-            if (x < win.min.x + margin && velocity.value.x < 0.0)
-                || (x > win.max.x - margin && velocity.value.x > 0.0)
-            {
-                new_velocity.x *= -1.0;
-            }
-            if (y < win.min.y + margin && velocity.value.y < 0.0)
-                || (y > win.max.y - margin && velocity.value.y > 0.0)
-            {
-                new_velocity.y *= -1.0;
-            }
+                // where the neighbour is seen from across a wrapping edge
+                let position = body.position - shift.extend(0.0);
+                let offset = boid.position - position;
+                let distance = offset.length();
+                if distance > 0.0 && distance < rules.personal_space {
+                    // falls off with distance, so the closest neighbours dominate
+                    push += offset / (distance * distance);
+                }
+                if body.flock == boid.flock {
+                    flockmates += 1;
+                    center += position;
+                    heading += body.velocity;
+                }
+            },
+        );
+    }
 
-            // -------------------- Random --------------------
-            if rand::thread_rng().gen_range(0..100) < 1 {
-                new_velocity.x += rand::thread_rng().gen_range(-10.0..10.0);
-                new_velocity.y += rand::thread_rng().gen_range(-10.0..10.0);
-            }
+    let mut acceleration = Vec3::ZERO;
+    if flockmates > 0 {
+        let count = flockmates as f32;
 
-            // -------------------- Damping --------------------
-            let delta_time = universe.speed * time.delta_seconds();
-            let mut damping = velocity.damping * delta_time;
-            if new_velocity.length() < velocity.min_speed {
-                damping *= 0.1;
-            }
-            new_velocity -= new_velocity.normalize() * damping;
+        // -------------------- Cohesion --------------------
+        acceleration += seek(center / count - boid.position, velocity, rules) * rules.cohesion;
 
-            // finally set the new velocity
-            velocity.value = new_velocity;
-        });
+        // -------------------- Alignment --------------------
+        acceleration += seek(heading / count, velocity, rules) * rules.alignment;
+    }
+
+    // -------------------- Separation --------------------
+    acceleration += seek(push, velocity, rules) * rules.separation;
+
+    // heavier debri answer the same forces more slowly
+    let mut acceleration = acceleration / boid.mass;
+
+    // -------------------- Gravity --------------------
+    // falls the same whatever the mass
+    for (position, well) in wells {
+        let offset = bounds.offset(&universe.bounds, *position, boid.position);
+        acceleration += well.acceleration(offset);
+    }
+    let mut new_velocity = (velocity + acceleration * delta_time).clamp_length_max(rules.max_speed);
+
+    // -------------------- Random --------------------
+    new_velocity += boid.jitter;
+
+    // -------------------- Damping --------------------
+    let mut damping = boid.velocity.damping * delta_time;
+    if new_velocity.length() < boid.velocity.min_speed {
+        damping *= 0.1;
+    }
+    new_velocity -= new_velocity.normalize_or_zero() * damping;
+
+    (nearby, new_velocity)
+}
+
+// Reynolds steering: the change that turns `velocity` into full speed along
+// `desired`, limited to the flock's max force.
+fn seek(desired: Vec3, velocity: Vec3, rules: &FlockRules) -> Vec3 {
+    if desired == Vec3::ZERO {
+        return Vec3::ZERO;
+    }
+    (desired.normalize() * rules.max_speed - velocity).clamp_length_max(rules.max_force)
 }
 
 pub fn move_system(
@@ -130,43 +271,250 @@ pub fn move_system(
     });
 }
 
-pub fn render_quadtree(_commands: Commands, universe: ResMut<DebriUniverse>, mut gizmos: Gizmos) {
-    let regions = universe.graph.get_regions();
-
-    regions.iter().for_each(|region| {
-        let (min_x, min_y, max_x, max_y) = region.into_f32();
-
-        let bottom_left = Vec3::new(min_x, min_y, 0.0);
-        let bottom_right = Vec3::new(max_x, min_y, 0.0);
-        let top_right = Vec3::new(max_x, max_y, 0.0);
-        let top_left = Vec3::new(min_x, max_y, 0.0);
-
-        gizmos.line(bottom_left, bottom_right, Color::WHITE);
-        gizmos.line(bottom_right, top_right, Color::WHITE);
-        gizmos.line(top_right, top_left, Color::WHITE);
-        gizmos.line(top_left, bottom_left, Color::WHITE);
-    })
-}
-
-pub fn despawn_debri(mut commands: Commands, projectile_query: Query<Entity, With<Debri>>) {
+pub fn despawn_debri(
+    mut commands: Commands,
+    projectile_query: Query<Entity, With<Debri>>,
+    mut universe: ResMut<DebriUniverse>,
+) {
     for entity in projectile_query.iter() {
-        commands.entity(entity).despawn();
+        commands.entity(entity).despawn_recursive();
     }
+    universe.graph.clear();
 }
 
+/// A debri a collector is chipping mass off.
+type Hauled = (
+    &'static mut Transform,
+    &'static mut Collider,
+    &'static mut Bulk,
+    &'static DebriKind,
+);
+
 pub fn handle_debri_collected_event(
     mut commands: Commands,
     mut events: EventReader<CollectedEvent>,
-    mut query: Query<(Entity, &mut Transform, &mut Collider, &mut Velocity), Without<Collector>>,
+    mut query: Query<Hauled, DebriOnly>,
     mut universe: ResMut<DebriUniverse>,
+    kinds: Res<DebriKinds>,
 ) {
     for event in events.read() {
         let entity = event.entity;
-        let collider = query.get_mut(entity);
-        if let Ok((_, _, collider, _)) = collider {
-            let slot_id = collider.id.as_ref().unwrap();
-            universe.graph.remove(&slot_id);
-            commands.entity(entity).despawn();
+        let Ok((mut transform, mut collider, mut bulk, kind)) = query.get_mut(entity) else {
+            continue;
+        };
+        bulk.take(event.mass);
+        if bulk.is_empty() {
+            if let Some(slot_id) = &collider.id {
+                universe.graph.remove(slot_id);
+            }
+            commands.entity(entity).despawn_recursive();
+        } else {
+            // too heavy for one trip, what is left shrinks
+            resize(&mut transform, &mut collider, kinds.rules(*kind), bulk.mass);
+        }
+    }
+}
+
+// Scales a debri's sprite and collider to fit `mass`.
+fn resize(transform: &mut Transform, collider: &mut Collider, rules: &DebriKindRules, mass: f32) {
+    collider.radius = rules.radius(mass);
+    transform.scale = Vec3::splat(collider.radius / rules.radius(rules.mass));
+}
+
+/// A debri that can absorb another one.
+type Merging = (
+    Entity,
+    &'static mut Transform,
+    &'static mut Collider,
+    &'static mut Velocity,
+    &'static mut Bulk,
+    &'static DebriKind,
+    Option<&'static mut Lifetime>,
+);
+
+/// Fuses touching debri. Every debri merges at most once per tick, the
+/// heavier one of a pair survives and takes the other's mass, value and
+/// momentum.
+pub fn merge_debri(
+    mut commands: Commands,
+    mut query: Query<Merging, DebriOnly>,
+    mut universe: ResMut<DebriUniverse>,
+    merging: Res<DebriMerging>,
+    kinds: Res<DebriKinds>,
+    mut rng: ResMut<GameRng>,
+) {
+    if merging.chance <= 0.0 {
+        return;
+    }
+
+    // -------------------- find touching pairs --------------------
+    let mut merged = HashSet::new();
+    let mut pairs = Vec::new();
+    for (entity, transform, collider, _, bulk, _, _) in query.iter() {
+        if merged.contains(&entity) {
+            continue;
+        }
+        let mut partner = None;
+        universe.graph.for_each_in(
+            &collider.into_region(transform.translation),
+            &mut |_, body| {
+                if partner.is_some() || body.entity == entity || merged.contains(&body.entity) {
+                    return;
+                }
+                let Ok((_, other_transform, other_collider, _, other_bulk, _, _)) =
+                    query.get(body.entity)
+                else {
+                    return;
+                };
+                let distance = transform.translation.distance(other_transform.translation);
+                if distance < collider.radius + other_collider.radius
+                    && bulk.mass + other_bulk.mass <= merging.max_mass
+                    && rng.gen::<f32>() < merging.chance
+                {
+                    partner = Some((body.entity, other_bulk.mass));
+                }
+            },
+        );
+        if let Some((partner, partner_mass)) = partner {
+            merged.insert(entity);
+            merged.insert(partner);
+            pairs.push(if partner_mass > bulk.mass {
+                (partner, entity)
+            } else {
+                (entity, partner)
+            });
+        }
+    }
+
+    // -------------------- merge --------------------
+    for (keep, absorb) in pairs {
+        let Ok([kept, absorbed]) = query.get_many_mut([keep, absorb]) else {
+            continue;
+        };
+        let (_, mut transform, mut collider, mut velocity, mut bulk, kind, lifetime) = kept;
+        let (_, other_transform, other_collider, other_velocity, other_bulk, _, other_lifetime) =
+            absorbed;
+
+        let mass = bulk.mass + other_bulk.mass;
+        transform.translation = (transform.translation * bulk.mass
+            + other_transform.translation * other_bulk.mass)
+            / mass;
+        velocity.value =
+            (velocity.value * bulk.mass + other_velocity.value * other_bulk.mass) / mass;
+        if let (Some(mut lifetime), Some(other_lifetime)) = (lifetime, other_lifetime) {
+            lifetime.age = (lifetime.age * bulk.mass + other_lifetime.age * other_bulk.mass) / mass;
+        }
+        bulk.mass = mass;
+        bulk.value += other_bulk.value;
+        resize(&mut transform, &mut collider, kinds.rules(*kind), mass);
+
+        if let Some(slot_id) = &other_collider.id {
+            universe.graph.remove(slot_id);
+        }
+        commands.entity(absorb).despawn_recursive();
+    }
+}
+
+pub fn switch_spatial_backend(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut universe: ResMut<DebriUniverse>,
+    mut query: Query<&mut Collider>,
+) {
+    if keyboard_input.just_pressed(KeyCode::B) {
+        let backend = universe.backend.next();
+        universe.set_backend(backend);
+        // ids from the old index mean nothing to the new one
+        for mut collider in query.iter_mut() {
+            collider.id = None;
+        }
+        println!("Spatial backend: {:?}", backend);
+    }
+}
+
+pub fn export_snapshot(
+    keyboard_input: Res<Input<KeyCode>>,
+    universe: Res<DebriUniverse>,
+    bench: Res<QuadBench>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F5) {
+        let snapshot = Snapshot::capture(&universe, bench.tick);
+        match snapshot.save(Path::new(SNAPSHOT_DIR)) {
+            Ok(path) => println!("Wrote quadtree snapshot to {}", path.display()),
+            Err(err) => println!("Could not write quadtree snapshot: {}", err),
+        }
+    }
+}
+
+pub fn write_bench_csv(mut exit_events: EventReader<AppExit>, bench: Res<QuadBench>) {
+    if exit_events.read().next().is_none() {
+        return;
+    }
+    if let Some(path) = &bench.csv_path {
+        match bench.write_csv(path) {
+            Ok(()) => println!("Wrote quadtree bench to {}", path.display()),
+            Err(err) => println!("Could not write quadtree bench: {}", err),
+        }
+    }
+}
+
+/// A mortal debri and the material it fades out through.
+type Aging = (
+    Entity,
+    &'static mut Lifetime,
+    &'static Collider,
+    &'static DebriKind,
+    &'static mut Handle<ColorMaterial>,
+);
+
+/// Ages debri with a `Lifetime`, fading them out and despawning them when
+/// their time is up.
+pub fn age_debri(
+    mut commands: Commands,
+    mut query: Query<Aging, With<Debri>>,
+    mut universe: ResMut<DebriUniverse>,
+    assets: Res<GameAssets>,
+    time: Res<Time>,
+) {
+    for (entity, mut lifetime, collider, kind, mut material) in query.iter_mut() {
+        lifetime.age += time.delta_seconds();
+        if lifetime.is_over() {
+            if let Some(slot_id) = &collider.id {
+                universe.graph.remove(slot_id);
+            }
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        // swap to the next shared fade level, only when it changes
+        let faded = assets.debri_material(*kind, lifetime.remaining());
+        if *material != *faded {
+            *material = faded.clone();
+        }
+    }
+}
+
+/// Holds debri to the `WorldBounds`, despawning those lost to open space.
+pub fn keep_debri_in_bounds(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform, &mut Velocity, &Collider), DebriOnly>,
+    mut universe: ResMut<DebriUniverse>,
+    bounds: Res<WorldBounds>,
+    time: Res<Time>,
+) {
+    let delta_time = universe.speed * time.delta_seconds();
+    for (entity, mut transform, mut velocity, collider) in query.iter_mut() {
+        let mut position = transform.translation;
+        match bounds.apply(&universe.bounds, &mut position, collider.radius) {
+            BoundsHit::Inside => {}
+            BoundsHit::Escaped => {
+                if let Some(slot_id) = &collider.id {
+                    universe.graph.remove(slot_id);
+                }
+                commands.entity(entity).despawn_recursive();
+            }
+            hit => {
+                transform.translation = position;
+                bounds.deflect(hit, &mut velocity.value, delta_time);
+            }
         }
     }
 }
@@ -175,42 +523,343 @@ pub fn count_debri(query: Query<&Debri, Without<Collected>>, mut universe: ResMu
     universe.debri_count = query.iter().count() as u32;
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_debri(
     mut commands: Commands,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut meshes: ResMut<Assets<Mesh>>,
+    assets: Res<GameAssets>,
     mut events: EventReader<SpawnDebri>,
+    params: Res<FlockParams>,
+    kinds: Res<DebriKinds>,
+    universe: Res<DebriUniverse>,
+    decay: Res<DebriDecay>,
+    mut rng: ResMut<GameRng>,
 ) {
     for event in events.read() {
-        let position = event.position.clone();
-        let direction = event.direction;
-        let initial_speed = 200.0;
-        // Negate and normalize the direction for debris velocity
-        let velocity = Vec3::new(
-            -direction.x * initial_speed * 1.2,
-            -direction.y * initial_speed * 1.2,
-            0.0,
-        );
+        let kind = kinds.pick(&mut *rng);
+        let rules = kinds.rules(kind);
+        let position = event.position;
+        // away from the hit and mostly along the core's surface, all turning
+        // the same way, so the well bends them into one ring
+        let outward = -event.direction.extend(0.0);
+        let along = Vec3::new(-outward.y, outward.x, 0.0);
+        let velocity =
+            (along + outward * DEBRI_LAUNCH_TILT).normalize_or_zero() * DEBRI_LAUNCH_SPEED;
 
-        commands
-            .spawn(MaterialMesh2dBundle {
-                // texture: assets.load("boid.png"),
-                mesh: meshes
-                    .add(Mesh::from(shape::Quad::new(Vec2::new(
-                        DEBRI_SIZE, DEBRI_SIZE,
-                    ))))
-                    .into(),
-                material: materials.add(ColorMaterial::from(Color::rgb(2., 2., 0.))),
-                // texture: assets.load("/files/assets/boid.png"),
-                transform: Transform::from_xyz(position.x, position.y, 0.0),
-                ..Default::default()
-            })
+        let mut debri = commands.spawn(MaterialMesh2dBundle {
+            mesh: assets.debri_mesh(kind).into(),
+            material: assets.debri_material(kind, 1.0).clone(),
+            transform: Transform::from_xyz(position.x, position.y, 0.0),
+            ..Default::default()
+        });
+        debri
             .insert(Debri)
             .insert(Velocity {
+                value: velocity,
+                damping: universe.damping,
+                min_speed: universe.min_speed,
+            })
+            .insert(Collider::new(rules.size / 2.0))
+            .insert(rules.bulk())
+            .insert(kind)
+            .insert(params.pick(&mut *rng));
+        if decay.lifetime > 0.0 {
+            debri.insert(Lifetime::new(decay.lifetime));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::resources::BoundsMode;
+    use crate::spatial::IndexStats;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::time::Duration;
+
+    fn physics_world(seed: u64) -> World {
+        let mut world = World::new();
+        world.insert_resource(DebriUniverse::new(Vec2::ZERO, Vec2::splat(400.0)));
+        world.insert_resource(FlockParams::default());
+        world.insert_resource(DebriKinds::default());
+        world.insert_resource(DebriMerging {
+            chance: 0.3,
+            ..default()
+        });
+        world.insert_resource(GameRng::new(seed));
+        world.insert_resource(WorldBounds::default());
+        world.insert_resource(QuadBench::default());
+        world.insert_resource(Time::<()>::default());
+        world
+    }
+
+    fn spawn(world: &mut World, kind: DebriKind, flock: Flock, position: Vec3, velocity: Vec3) {
+        let rules = *world.resource::<DebriKinds>().rules(kind);
+        world.spawn((
+            Transform::from_translation(position),
+            Debri,
+            Velocity {
                 value: velocity,
                 damping: 50.0,
                 min_speed: 50.0,
-            })
-            .insert(Collider::new(DEBRI_SIZE));
+            },
+            Collider::new(rules.size / 2.0),
+            flock,
+            kind,
+            rules.bulk(),
+        ));
+    }
+
+    fn run_ticks(world: &mut World, ticks: usize) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                build_or_update_quadtree,
+                merge_debri,
+                update_debri,
+                move_system,
+            )
+                .chain(),
+        );
+        for _ in 0..ticks {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_secs_f32(1.0 / 90.0));
+            schedule.run(world);
+        }
+    }
+
+    // Runs the physics tick on a seeded world and returns where every body
+    // ended up.
+    fn simulate(seed: u64) -> Vec<(Vec3, Vec3)> {
+        let mut world = physics_world(seed);
+        let params = FlockParams::default();
+        let kinds = DebriKinds::default();
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..300 {
+            let position = Vec3::new(rng.gen_range(0.0..400.0), rng.gen_range(0.0..400.0), 0.0);
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let velocity = Vec3::new(angle.cos(), angle.sin(), 0.0) * 200.0;
+            let (kind, flock) = (kinds.pick(&mut rng), params.pick(&mut rng));
+            spawn(&mut world, kind, flock, position, velocity);
+        }
+        run_ticks(&mut world, 60);
+
+        let mut bodies: Vec<_> = world
+            .query::<(Entity, &Transform, &Velocity)>()
+            .iter(&world)
+            .map(|(entity, transform, velocity)| (entity, transform.translation, velocity.value))
+            .collect();
+        bodies.sort_by_key(|(entity, _, _)| *entity);
+        bodies
+            .into_iter()
+            .map(|(_, position, velocity)| (position, velocity))
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_replays_bit_for_bit() {
+        let first = simulate(3);
+        let second = simulate(3);
+        assert_eq!(first.len(), second.len());
+        assert!(first.iter().zip(&second).all(|(a, b)| {
+            a.0.to_array().map(f32::to_bits) == b.0.to_array().map(f32::to_bits)
+                && a.1.to_array().map(f32::to_bits) == b.1.to_array().map(f32::to_bits)
+        }));
+        assert_ne!(first, simulate(4));
+    }
+
+    #[test]
+    fn touching_debri_merge_into_the_heavier_one() {
+        let mut world = physics_world(1);
+        world.resource_mut::<DebriMerging>().chance = 1.0;
+        let position = Vec3::new(200.0, 200.0, 0.0);
+        spawn(
+            &mut world,
+            DebriKind::Scrap,
+            Flock(0),
+            position,
+            Vec3::X * 100.0,
+        );
+        spawn(
+            &mut world,
+            DebriKind::Ore,
+            Flock(0),
+            position + Vec3::X,
+            Vec3::Y * 100.0,
+        );
+        run_ticks(&mut world, 1);
+
+        let kinds = DebriKinds::default();
+        let (scrap, ore) = (kinds.rules(DebriKind::Scrap), kinds.rules(DebriKind::Ore));
+        let mut survivors = world.query::<(&Bulk, &DebriKind, &Collider)>();
+        let survivors: Vec<_> = survivors.iter(&world).collect();
+        assert_eq!(survivors.len(), 1);
+        let (bulk, kind, collider) = survivors[0];
+        assert_eq!(*kind, DebriKind::Ore);
+        assert_eq!(bulk.mass, scrap.mass + ore.mass);
+        assert_eq!(bulk.value, scrap.value + ore.value);
+        assert!(collider.radius > ore.size / 2.0);
+    }
+
+    #[test]
+    fn expired_and_off_world_debri_are_despawned() {
+        let mut world = physics_world(1);
+        world.insert_resource(WorldBounds {
+            mode: BoundsMode::Open { margin: 0.0 },
+        });
+        let assets = GameAssets::new(
+            &DebriKinds::default(),
+            &mut Assets::default(),
+            &mut Assets::default(),
+        );
+        let center = Vec3::new(200.0, 200.0, 0.0);
+        spawn(&mut world, DebriKind::Scrap, Flock(0), center, Vec3::ZERO);
+        spawn(
+            &mut world,
+            DebriKind::Scrap,
+            Flock(0),
+            center + Vec3::X * 40.0,
+            Vec3::ZERO,
+        );
+        spawn(
+            &mut world,
+            DebriKind::Scrap,
+            Flock(0),
+            Vec3::splat(-100.0),
+            Vec3::ZERO,
+        );
+        let mut query = world.query_filtered::<Entity, With<Debri>>();
+        let entities: Vec<Entity> = query.iter(&world).collect();
+        for (index, entity) in entities.iter().enumerate() {
+            let material = assets.debri_material(DebriKind::Scrap, 1.0).clone();
+            // the first one is about to expire, the second one is young
+            let span = if index == 0 { 0.01 } else { 60.0 };
+            world
+                .entity_mut(*entity)
+                .insert((material, Lifetime::new(span)));
+        }
+        world.insert_resource(assets);
+
+        run_ticks(&mut world, 1);
+        let mut schedule = Schedule::default();
+        schedule
+            .add_systems((age_debri, keep_debri_in_bounds, apply_deferred, count_debri).chain());
+        schedule.run(&mut world);
+
+        let survivors: Vec<Entity> = query.iter(&world).collect();
+        assert_eq!(survivors, vec![entities[1]]);
+        assert_eq!(world.resource::<DebriUniverse>().debri_count, 1);
+        assert_eq!(world.resource::<DebriUniverse>().graph.value_count(), 1);
+    }
+
+    #[test]
+    fn gravity_wells_pull_debri_in() {
+        let mut world = physics_world(1);
+        let center = Vec3::new(200.0, 200.0, 0.0);
+        world.spawn((Transform::from_translation(center), GravityWell::default()));
+        let start = center + Vec3::X * 150.0;
+        spawn(&mut world, DebriKind::Ore, Flock(0), start, Vec3::Y);
+        run_ticks(&mut world, 30);
+
+        let mut debri = world.query_filtered::<(&Transform, &Velocity), With<Debri>>();
+        let (transform, velocity) = debri.single(&world);
+        assert!(transform.translation.distance(center) < start.distance(center));
+        assert!(velocity.value.x < 0.0);
+    }
+
+    // Flies a lone boid around the default well, steered like the physics
+    // tick does but without the random nudges and undamped, since damping
+    // bleeds any orbit away. Returns the closest and farthest it got.
+    fn orbit(offset: Vec3, velocity: Vec3, ticks: usize) -> (f32, f32) {
+        let universe = DebriUniverse::new(Vec2::ZERO, Vec2::splat(400.0));
+        let center = Vec3::new(200.0, 200.0, 0.0);
+        let wells = [(center, GravityWell::default())];
+        let mut boid = Boid {
+            entity: Entity::PLACEHOLDER,
+            flock: Flock(0),
+            position: center + offset,
+            radius: 4.0,
+            mass: 1.0,
+            velocity: Velocity {
+                value: velocity,
+                damping: 0.0,
+                min_speed: 0.0,
+            },
+            jitter: Vec3::ZERO,
+        };
+
+        let delta_time = 1.0 / 90.0;
+        let (mut closest, mut farthest) = (f32::MAX, 0.0_f32);
+        for _ in 0..ticks {
+            let (_, velocity) = steer(
+                &universe,
+                &WorldBounds::default(),
+                &wells,
+                &FlockRules::default(),
+                &boid,
+                delta_time,
+            );
+            boid.velocity.value = velocity;
+            boid.position += velocity * delta_time;
+            let distance = boid.position.distance(center);
+            closest = closest.min(distance);
+            farthest = farthest.max(distance);
+        }
+        (closest, farthest)
+    }
+
+    #[test]
+    fn debri_at_circular_speed_stay_in_orbit() {
+        let radius = 100.0;
+        let speed = (GravityWell::default().mass / radius).sqrt();
+        // a few laps
+        let (closest, farthest) = orbit(Vec3::X * radius, Vec3::Y * speed, 1500);
+        assert!(closest > 95.0 && farthest < 105.0, "{closest}..{farthest}");
+    }
+
+    #[test]
+    fn launched_debri_swing_out_of_the_core_and_back() {
+        let core = GravityWell::default().radius;
+        let velocity = (Vec3::Y + Vec3::X * DEBRI_LAUNCH_TILT).normalize() * DEBRI_LAUNCH_SPEED;
+        let (closest, farthest) = orbit(Vec3::X * core, velocity, 1500);
+        assert!(farthest > core * 2.0, "stuck in the core at {farthest}");
+        assert!(farthest < core * 5.0, "flew off to {farthest}");
+        assert!(closest > core / 2.0, "fell to {closest}");
+    }
+
+    #[test]
+    fn bench_rows_pair_a_ticks_build_and_query() {
+        let stats = IndexStats::default();
+        let mut bench = QuadBench::default();
+        bench.record_build(Duration::from_micros(5), 1, stats);
+        bench.record_query(Duration::from_micros(7));
+        assert_eq!(bench.tick, 1);
+        assert!(bench.history.is_empty());
+
+        bench.csv_path = Some("bench.csv".into());
+        bench.record_build(Duration::from_micros(11), 1, stats);
+        bench.record_query(Duration::from_micros(13));
+        bench.record_build(Duration::from_micros(17), 1, stats);
+        bench.record_query(Duration::from_micros(19));
+        let rows: Vec<_> = bench
+            .history
+            .iter()
+            .map(|record| (record.tick, record.build_time, record.query_time))
+            .collect();
+        assert_eq!(rows, vec![(2, 11, 13), (3, 17, 19)]);
+    }
+
+    #[test]
+    fn heavy_debri_are_hauled_in_several_trips() {
+        let mut bulk = Bulk {
+            mass: 7.0,
+            value: 10,
+        };
+        let first = bulk.take(3.0);
+        let second = bulk.take(3.0);
+        let last = bulk.take(3.0);
+        assert!(bulk.is_empty());
+        assert_eq!(first.value + second.value + last.value, 10);
+        assert_eq!(last.mass, 1.0);
     }
 }
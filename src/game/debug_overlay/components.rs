@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Text child of a debri showing its `Collider.nearby` count.
+#[derive(Component)]
+pub struct NearbyLabel;
@@ -0,0 +1,32 @@
+pub mod components;
+pub mod resources;
+mod systems;
+
+use resources::DebugOverlay;
+use systems::*;
+
+use crate::AppState;
+
+use bevy::prelude::*;
+
+pub struct DebugOverlayPlugin;
+
+impl Plugin for DebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugOverlay>()
+            // Systems
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_overlay,
+                    render_quadtree,
+                    render_heatmap,
+                    render_vision,
+                    update_nearby_labels,
+                )
+                    .run_if(in_state(AppState::Game)),
+            )
+            // On Exit State
+            .add_systems(OnExit(AppState::Game), despawn_heatmap);
+    }
+}
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+
+/// Which debug layers are drawn. The quadtree outline is toggled through
+/// `DebriUniverse.show_graph`.
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub show_heatmap: bool,
+    pub show_vision: bool,
+}
@@ -0,0 +1,204 @@
+use bevy::prelude::*;
+
+use super::{components::NearbyLabel, resources::DebugOverlay};
+use crate::game::debri::{
+    components::{Collider, Debri, Flock, QuadNodeRect},
+    resources::{DebriUniverse, FlockParams, QuadBench},
+};
+
+const LABEL_FONT_SIZE: f32 = 10.0;
+const LABEL_OFFSET: Vec3 = Vec3::new(0.0, 8.0, 1.0);
+/// Neighbour count drawn fully red in the vision overlay.
+const CROWDED: f32 = 16.0;
+
+pub fn toggle_debug_overlay(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut universe: ResMut<DebriUniverse>,
+    mut overlay: ResMut<DebugOverlay>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F6) {
+        universe.show_graph = !universe.show_graph;
+    }
+    if keyboard_input.just_pressed(KeyCode::F7) {
+        overlay.show_heatmap = !overlay.show_heatmap;
+    }
+    if keyboard_input.just_pressed(KeyCode::F8) {
+        overlay.show_vision = !overlay.show_vision;
+    }
+}
+
+pub fn render_quadtree(universe: Res<DebriUniverse>, mut gizmos: Gizmos) {
+    if !universe.show_graph {
+        return;
+    }
+
+    universe.graph.regions().iter().for_each(|region| {
+        let bottom_left = Vec3::new(region.min.x, region.min.y, 0.0);
+        let bottom_right = Vec3::new(region.max.x, region.min.y, 0.0);
+        let top_right = Vec3::new(region.max.x, region.max.y, 0.0);
+        let top_left = Vec3::new(region.min.x, region.max.y, 0.0);
+
+        gizmos.line(bottom_left, bottom_right, Color::WHITE);
+        gizmos.line(bottom_right, top_right, Color::WHITE);
+        gizmos.line(top_right, top_left, Color::WHITE);
+        gizmos.line(top_left, bottom_left, Color::WHITE);
+    })
+}
+
+// Redraws one sprite per occupied leaf once per physics tick, shaded from
+// blue to red relative to the fullest leaf. The last tick's sprites are
+// reused, only the surplus is spawned or despawned.
+pub fn render_heatmap(
+    mut commands: Commands,
+    universe: Res<DebriUniverse>,
+    bench: Res<QuadBench>,
+    overlay: Res<DebugOverlay>,
+    mut drawn_tick: Local<Option<u64>>,
+    mut cell_query: Query<(Entity, &mut Sprite, &mut Transform), With<QuadNodeRect>>,
+) {
+    if !overlay.show_heatmap {
+        for (entity, _, _) in cell_query.iter() {
+            commands.entity(entity).despawn();
+        }
+        *drawn_tick = None;
+        return;
+    }
+    if *drawn_tick == Some(bench.tick) {
+        return;
+    }
+    *drawn_tick = Some(bench.tick);
+
+    let mut leaves = Vec::new();
+    universe
+        .graph
+        .for_each_leaf(&mut |region, _, values| leaves.push((*region, values)));
+    let fullest = leaves
+        .iter()
+        .map(|(_, values)| *values)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let mut cells = cell_query.iter_mut();
+    for (region, values) in leaves.into_iter().filter(|(_, values)| *values > 0) {
+        let heat = values as f32 / fullest as f32;
+        let color = Color::rgba(heat, 0.0, 1.0 - heat, 0.35);
+        let translation = region.center().extend(-1.0);
+        match cells.next() {
+            Some((_, mut sprite, mut transform)) => {
+                sprite.color = color;
+                sprite.custom_size = Some(region.size());
+                transform.translation = translation;
+            }
+            None => {
+                commands.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color,
+                            custom_size: Some(region.size()),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(translation),
+                        ..default()
+                    },
+                    QuadNodeRect,
+                ));
+            }
+        }
+    }
+    for (entity, _, _) in cells {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn despawn_heatmap(mut commands: Commands, cell_query: Query<Entity, With<QuadNodeRect>>) {
+    for entity in cell_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn render_vision(
+    params: Res<FlockParams>,
+    overlay: Res<DebugOverlay>,
+    query: Query<(&Transform, &Collider, &Flock), With<Debri>>,
+    mut gizmos: Gizmos,
+) {
+    if !overlay.show_vision {
+        return;
+    }
+
+    for (transform, collider, flock) in query.iter() {
+        let crowd = (collider.nearby as f32 / CROWDED).min(1.0);
+        gizmos.circle_2d(
+            transform.translation.truncate(),
+            params.rules(*flock).vision_radius(collider.radius),
+            Color::rgb(crowd, 1.0 - crowd, 0.0),
+        );
+    }
+}
+
+/// A debri and the label child it may already have.
+type Labelled = (
+    Entity,
+    &'static Transform,
+    &'static Collider,
+    Option<&'static Children>,
+);
+
+/// The text and placement of a neighbour count label.
+type Label = (Entity, &'static mut Text, &'static mut Transform);
+
+pub fn update_nearby_labels(
+    mut commands: Commands,
+    overlay: Res<DebugOverlay>,
+    asset_server: Res<AssetServer>,
+    debri_query: Query<Labelled, With<Debri>>,
+    mut label_query: Query<Label, (With<NearbyLabel>, Without<Debri>)>,
+) {
+    if !overlay.show_vision {
+        for (entity, _, _) in label_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    for (entity, transform, collider, children) in debri_query.iter() {
+        let label = children
+            .and_then(|children| children.iter().find(|child| label_query.contains(**child)))
+            .copied();
+        // debri rotate with their heading, keep the label upright above them
+        let upright = transform.rotation.inverse();
+
+        match label {
+            Some(label) => {
+                let (_, mut text, mut label_transform) = label_query.get_mut(label).unwrap();
+                text.sections[0].value = collider.nearby.to_string();
+                label_transform.translation = upright * LABEL_OFFSET;
+                label_transform.rotation = upright;
+            }
+            None => {
+                commands.entity(entity).with_children(|parent| {
+                    parent.spawn((
+                        Text2dBundle {
+                            text: Text::from_section(
+                                collider.nearby.to_string(),
+                                TextStyle {
+                                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                                    font_size: LABEL_FONT_SIZE,
+                                    color: Color::WHITE,
+                                },
+                            ),
+                            transform: Transform {
+                                translation: upright * LABEL_OFFSET,
+                                rotation: upright,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        NearbyLabel,
+                    ));
+                });
+            }
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use bevy::prelude::*;
 
 #[derive(Component)]
-
-pub struct Enemy;
+pub struct Enemy {
+    pub stash_pos: Transform,
+    pub returning: bool,
+    pub carrying: Option<f32>,
+}
 
 #[derive(Event)]
 pub struct EnemySpawnEvent {
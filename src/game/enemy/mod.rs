@@ -3,11 +3,10 @@ mod systems;
 
 use self::components::EnemySpawnEvent;
 
-use super::{debri::PHYISCS_TICK_RATE, SimulationState};
+use super::SimulationSet;
 use crate::AppState;
 
-use bevy::{prelude::*, time::common_conditions::on_timer};
-use instant::Duration;
+use bevy::prelude::*;
 use systems::*;
 
 pub const ENEMY_SIZE: f32 = 10.0;
@@ -22,10 +21,9 @@ impl Plugin for EnemyPlugin {
             // Systems
             .add_systems(
                 FixedUpdate,
-                (enemy_movement, spawn_enemy)
-                    .run_if(on_timer(Duration::from_secs_f32(1. / PHYISCS_TICK_RATE)))
-                    .run_if(in_state(AppState::Game))
-                    .run_if(in_state(SimulationState::Running)),
+                (spawn_enemy, enemy_movement)
+                    .chain()
+                    .in_set(SimulationSet::Enemies),
             )
             // On Exit State
             .add_systems(OnExit(AppState::Game), despawn_enemy);
@@ -4,12 +4,13 @@ use crate::game::{
         components::{Collected, CollectedEvent},
         resources::DebriUniverse,
     },
+    resources::{GameAssets, GameRng},
 };
 use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
-use rand::prelude::ThreadRng;
 use rand::Rng;
 
-use crate::game::{debri::components::Collider, score::resources::Score};
+use crate::game::debri::components::Collider;
+use crate::quadtree::layers::Layers;
 
 use super::{
     components::{Enemy, EnemySpawnEvent},
@@ -18,24 +19,19 @@ use super::{
 
 pub fn enemy_movement(
     mut query: Query<(&mut Transform, &mut Enemy, &Collider, &mut Velocity), Without<Collected>>,
-    mut score: ResMut<Score>,
-    enemy_query: Query<&Collider, With<Enemy>>,
     universe: Res<DebriUniverse>,
     time: Res<Time>,
+    mut rng: ResMut<GameRng>,
     mut events: EventWriter<CollectedEvent>,
 ) {
-    let mut rng = ThreadRng::default();
-    let exclude_ids = enemy_query
-        .iter()
-        .filter_map(|collider| collider.id.clone())
-        .collect::<Vec<_>>();
-
     for (mut transform, mut enemy, collider, velocity) in query.iter_mut() {
         // -------------------- collision query --------------------
         let query_region = collider
             .into_region(transform.translation)
-            .with_margin((universe.vision * 4000.0) as i32);
-        let collisions = universe.graph.query(&query_region, &exclude_ids);
+            .with_margin(universe.vision * 4000.0);
+        let collisions = universe
+            .graph
+            .query_filtered(&query_region, Layers::DEBRI, &|_| true);
 
         // move towards any debri in range
         if let Some(nearest) = collisions
@@ -60,6 +56,7 @@ pub fn enemy_movement(
             if distance < ENEMY_SIZE {
                 events.send(CollectedEvent {
                     entity: nearest.entity,
+                    mass: f32::INFINITY,
                 });
 
                 enemy.returning = true;
@@ -81,14 +78,13 @@ pub fn despawn_enemy(mut commands: Commands, query: Query<Entity, With<Enemy>>)
 pub fn spawn_enemy(
     mut events: EventReader<EnemySpawnEvent>,
     mut commands: Commands,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut meshes: ResMut<Assets<Mesh>>,
+    assets: Res<GameAssets>,
 ) {
     for event in events.read() {
         commands.spawn((
             MaterialMesh2dBundle {
-                mesh: meshes.add(shape::Circle::new(ENEMY_SIZE).into()).into(),
-                material: materials.add(ColorMaterial::from(Color::RED)),
+                mesh: assets.enemy_mesh.clone().into(),
+                material: assets.enemy_material.clone(),
                 transform: Transform::from_xyz(
                     event.spawn_pos.translation.x,
                     event.spawn_pos.translation.y,
@@ -1,23 +1,25 @@
+pub mod building;
+pub mod collector;
 pub mod components;
 pub mod debri;
+mod debug_overlay;
 pub mod enemy;
 pub mod player;
 mod projectile;
+pub mod resources;
 pub mod score;
 pub mod star;
-pub mod collector;
-pub mod building;
 mod systems;
 mod target;
 mod ui;
 
-use enemy::EnemyPlugin;
 use building::BuildingPlugin;
+use collector::CollectorPlugin;
+use debug_overlay::DebugOverlayPlugin;
 use player::PlayerPlugin;
 use projectile::ProjectilePlugin;
+use resources::{GameRng, WorldBounds, WorldConfig};
 use score::ScorePlugin;
-use collector::CollectorPlugin;
-use star::StarPlugin;
 use systems::*;
 use target::TargetPlugin;
 use ui::GameUIPlugin;
@@ -36,10 +38,30 @@ impl Plugin for GamePlugin {
         app
             // Events
             .add_event::<GameOver>()
+            // Resources
+            .insert_resource(GameRng::from_env())
+            .insert_resource(WorldConfig::from_env())
+            .init_resource::<WorldBounds>()
             // States
             .add_state::<SimulationState>()
+            // Fixed Update Sets
+            .configure_sets(
+                FixedUpdate,
+                (
+                    SimulationSet::Projectiles,
+                    SimulationSet::Debri,
+                    SimulationSet::Collectors,
+                    SimulationSet::Enemies,
+                    SimulationSet::Player,
+                    SimulationSet::Cleanup,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Game))
+                    .run_if(in_state(SimulationState::Running)),
+            )
             // OnEnter Systems
             // .add_systems(OnEnter(AppState::Game), pause_simulation)
+            .add_systems(OnEnter(AppState::Game), insert_game_assets)
             // My Plugins
             .add_plugins((
                 // EnemyPlugin,
@@ -52,14 +74,19 @@ impl Plugin for GamePlugin {
                 CollectorPlugin,
                 // StarPlugin,
                 GameUIPlugin,
+                DebugOverlayPlugin,
             ))
             // Systems
+            .add_systems(FixedUpdate, keep_in_bounds.in_set(SimulationSet::Cleanup))
             .add_systems(
                 Update,
-                (toggle_simulation).run_if(in_state(AppState::Game)),
+                (toggle_simulation, cycle_world_bounds).run_if(in_state(AppState::Game)),
             )
             // Exit State Systems
-            .add_systems(OnExit(AppState::Game), resume_simulation);
+            .add_systems(
+                OnExit(AppState::Game),
+                (resume_simulation, reset_game_rng, remove_game_assets),
+            );
     }
 }
 
@@ -69,3 +96,16 @@ pub enum SimulationState {
     Running,
     Paused,
 }
+
+/// Stages of the fixed timestep, run one after the other. Systems inside a
+/// stage that touch the same data are chained by their plugin.
+#[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SimulationSet {
+    Projectiles,
+    Debri,
+    Collectors,
+    Enemies,
+    Player,
+    /// Despawns and counts whatever the stages above collected.
+    Cleanup,
+}
@@ -3,7 +3,7 @@ mod systems;
 
 use systems::*;
 
-use super::SimulationState;
+use super::SimulationSet;
 use crate::AppState;
 
 use bevy::prelude::*;
@@ -16,12 +16,7 @@ impl Plugin for PlayerPlugin {
             // On Enter State
             .add_systems(OnEnter(AppState::Game), spawn_player)
             // Systems
-            .add_systems(
-                FixedUpdate,
-                (orbit_system,)
-                    .run_if(in_state(AppState::Game))
-                    .run_if(in_state(SimulationState::Running)),
-            )
+            .add_systems(FixedUpdate, (orbit_system,).in_set(SimulationSet::Player))
             // On Exit State
             .add_systems(OnExit(AppState::Game), despawn_player);
     }
@@ -1,11 +1,12 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
+use rand::Rng;
 
 use super::components::Player;
 
+use crate::game::components::Bounded;
 use crate::game::components::OrbitCenter;
 use crate::game::components::Velocity;
-use crate::game::debri::components::Collider;
+use crate::game::resources::{GameRng, WorldConfig};
 
 pub fn orbit_system(
     time: Res<Time>,
@@ -33,21 +34,25 @@ pub fn orbit_system(
 
 pub fn spawn_player(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    config: Res<WorldConfig>,
     asset_server: Res<AssetServer>,
+    mut rng: ResMut<GameRng>,
 ) {
-    let window = window_query.get_single().unwrap();
-    let initial_speed = rand::random::<f32>() * 0.5;
+    let (width, height) = (config.size.x, config.size.y);
+    let initial_speed = rng.gen::<f32>() * 0.5;
     let velocity = Vec3::new(
-        (rand::random::<f32>() - 0.5) * initial_speed,
-        (rand::random::<f32>() - 0.5) * initial_speed,
+        (rng.gen::<f32>() - 0.5) * initial_speed,
+        (rng.gen::<f32>() - 0.5) * initial_speed,
         0.0,
     );
 
-    // random position in a circle of the radius of the window
+    // random position in a circle of the radius of the world, so the whole
+    // orbit stays inside the bounds
+    let orbit_radius = rng.gen::<f32>() * width.min(height) / 2.0;
+    let angle = rng.gen::<f32>() * std::f32::consts::TAU;
     let position = Transform::from_xyz(
-        window.width() / 2.0 + (rand::random::<f32>() - 0.5) * window.width(),
-        window.height() / 2.0 + (rand::random::<f32>() - 0.5) * window.height(),
+        width / 2.0 + orbit_radius * angle.cos(),
+        height / 2.0 + orbit_radius * angle.sin(),
         0.0,
     );
 
@@ -58,14 +63,15 @@ pub fn spawn_player(
             ..default()
         },
         Player,
+        Bounded,
         Velocity {
             value: velocity,
             damping: 2.0,
             min_speed: 50.0,
         },
         OrbitCenter {
-            x: window.width() / 2.0,
-            y: window.height() / 2.0,
+            x: width / 2.0,
+            y: height / 2.0,
         },
     ));
 
@@ -2,14 +2,16 @@ pub mod components;
 pub mod resources;
 mod systems;
 
-use systems::*;
 use resources::*;
+use systems::*;
 
-use super::SimulationState;
+use super::SimulationSet;
 use crate::AppState;
 
 use bevy::prelude::*;
 
+pub const PROJECTILE_SIZE: Vec2 = Vec2::new(12.0, 2.0);
+
 pub struct ProjectilePlugin;
 
 impl Plugin for ProjectilePlugin {
@@ -21,20 +23,13 @@ impl Plugin for ProjectilePlugin {
             .add_systems(
                 FixedUpdate,
                 (
-                    projectile_hit_target,
-                    projectile_movement,
-                )
-                    .run_if(in_state(AppState::Game))
-                    .run_if(in_state(SimulationState::Running)),
-            )
-            .add_systems(
-                Update,
-                (
-                    spawn_projectile_timer,
                     tick_projectile_spawn_timer,
+                    spawn_projectile_timer,
+                    projectile_movement,
+                    projectile_hit_target,
                 )
-                    .run_if(in_state(AppState::Game))
-                    .run_if(in_state(SimulationState::Running)),
+                    .chain()
+                    .in_set(SimulationSet::Projectiles),
             )
             // On Exit State
             .add_systems(OnExit(AppState::Game), despawn_projectile);
@@ -5,6 +5,8 @@ pub const PROJECTILE_SPAWN_TIME: f32 = 0.70;
 #[derive(Resource)]
 pub struct ProjectileSpawnTimer {
     pub timer: Timer,
+    // nothing scales the spawn rate yet
+    #[allow(dead_code)]
     pub spawn_multiplier: f32,
 }
 
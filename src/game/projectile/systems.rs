@@ -1,8 +1,9 @@
 use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
 
-use crate::game::components::Position;
-use crate::game::debri::components::SpawnDebri;
+use crate::game::components::{Bounded, GravityWell, Position};
+use crate::game::debri::{components::SpawnDebri, resources::DebriUniverse};
 use crate::game::player::components::Player;
+use crate::game::resources::{GameAssets, WorldBounds};
 use crate::game::target::components::Target;
 
 use super::components::Projectile;
@@ -10,12 +11,15 @@ use super::resources::*;
 
 pub fn projectile_movement(
     mut projectile_query: Query<(&mut Transform, &Projectile)>,
+    universe: Res<DebriUniverse>,
+    bounds: Res<WorldBounds>,
     time: Res<Time>,
 ) {
     projectile_query
         .par_iter_mut()
         .for_each(|(mut transform, projectile)| {
-            let direction = projectile.target - transform.translation;
+            let direction =
+                bounds.offset(&universe.bounds, transform.translation, projectile.target);
             let velocity = direction.normalize() * time.delta_seconds() * 200.0;
             transform.translation += velocity;
         });
@@ -42,8 +46,7 @@ pub fn spawn_projectile_timer(
     projectile_spawn_timer: Res<ProjectileSpawnTimer>,
     player_query: Query<&Transform, With<Player>>,
     target_query: Query<&Transform, With<Target>>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    assets: Res<GameAssets>,
 ) {
     if projectile_spawn_timer.timer.finished() {
         let target_transform = target_query.get_single().unwrap();
@@ -54,10 +57,8 @@ pub fn spawn_projectile_timer(
 
             commands.spawn((
                 MaterialMesh2dBundle {
-                    mesh: meshes
-                        .add(Mesh::from(shape::Quad::new(Vec2::new(12.0, 2.0))))
-                        .into(),
-                    material: materials.add(ColorMaterial::from(Color::RED)),
+                    mesh: assets.projectile_mesh.clone().into(),
+                    material: assets.projectile_material.clone(),
                     transform: Transform {
                         translation: Vec3::new(
                             player_transform.translation.x,
@@ -72,6 +73,7 @@ pub fn spawn_projectile_timer(
                 Projectile {
                     target: target_transform.translation,
                 },
+                Bounded,
             ));
         }
     }
@@ -81,10 +83,10 @@ pub fn projectile_hit_target(
     mut commands: Commands,
     mut events_writer: EventWriter<SpawnDebri>,
     mut projectile_query: Query<(Entity, &Transform), With<Projectile>>,
-    target_query: Query<&Transform, With<Target>>,
+    target_query: Query<(&Transform, Option<&GravityWell>), With<Target>>,
 ) {
     for (entity, projectile_transform) in projectile_query.iter_mut() {
-        if let Ok(target_transform) = target_query.get_single() {
+        if let Ok((target_transform, well)) = target_query.get_single() {
             let distance = projectile_transform
                 .translation
                 .distance(target_transform.translation);
@@ -92,9 +94,12 @@ pub fn projectile_hit_target(
                 // Direction from target to projectile
                 let direction = projectile_transform.translation - target_transform.translation;
                 let direction = Vec2::new(direction.x, direction.y).normalize();
+                // chipped off the far side of the core, where the well's pull
+                // stops growing, so they can get away from it
+                let core = well.map_or(0.0, |well| well.radius);
                 let position = Position {
-                    x: target_transform.translation.x,
-                    y: target_transform.translation.y,
+                    x: target_transform.translation.x - direction.x * core,
+                    y: target_transform.translation.y - direction.y * core,
                 };
 
                 // Spawn debris
@@ -0,0 +1,488 @@
+use bevy::prelude::*;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+use super::building::BUILDING_SIZE;
+use super::collector::COLLECTOR_SIZE;
+use super::debri::{components::DebriKind, resources::DebriKinds, FADE_LEVELS};
+use super::enemy::ENEMY_SIZE;
+use super::projectile::PROJECTILE_SIZE;
+use crate::quadtree::region::Region;
+
+/// Seed used when `GAME_SEED` is not set.
+pub const DEFAULT_SEED: u64 = 0x5eed;
+/// Set to a number to pick the seed the simulation runs with.
+pub const SEED_ENV: &str = "GAME_SEED";
+
+/// The only source of randomness the simulation may use. Systems that draw
+/// from it must run in a fixed order, so the same seed and inputs replay the
+/// same world.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let seed = std::env::var(SEED_ENV)
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or(DEFAULT_SEED);
+        Self::new(seed)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Starts the sequence over from the seed.
+    pub fn reset(&mut self) {
+        self.rng = StdRng::seed_from_u64(self.seed);
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+/// Meshes and materials shared by every entity of a kind, so spawning adds no
+/// assets and entities of a kind draw in one batch. Removed when the game
+/// ends, which frees the assets once their entities are despawned too.
+#[derive(Resource)]
+pub struct GameAssets {
+    pub collector_mesh: Handle<Mesh>,
+    pub collector_material: Handle<ColorMaterial>,
+    pub enemy_mesh: Handle<Mesh>,
+    pub enemy_material: Handle<ColorMaterial>,
+    pub projectile_mesh: Handle<Mesh>,
+    pub projectile_material: Handle<ColorMaterial>,
+    pub building_mesh: Handle<Mesh>,
+    pub building_material: Handle<ColorMaterial>,
+    debri_meshes: Vec<Handle<Mesh>>,
+    /// `FADE_LEVELS + 1` colour variants per `DebriKind`, from transparent
+    /// to opaque.
+    debri_materials: Vec<Vec<Handle<ColorMaterial>>>,
+}
+
+impl GameAssets {
+    pub fn new(
+        kinds: &DebriKinds,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<ColorMaterial>,
+    ) -> Self {
+        let collector_mesh = meshes.add(shape::Circle::new(COLLECTOR_SIZE).into());
+        let enemy_mesh = meshes.add(shape::Circle::new(ENEMY_SIZE).into());
+        let projectile_mesh = meshes.add(Mesh::from(shape::Quad::new(PROJECTILE_SIZE)));
+        let building_mesh = meshes.add(
+            shape::Cube {
+                size: BUILDING_SIZE,
+            }
+            .into(),
+        );
+        let debri_meshes = kinds
+            .kinds
+            .iter()
+            .map(|rules| meshes.add(Mesh::from(shape::Quad::new(Vec2::splat(rules.size)))))
+            .collect();
+        let debri_materials = kinds
+            .kinds
+            .iter()
+            .map(|rules| {
+                (0..=FADE_LEVELS)
+                    .map(|level| {
+                        let alpha = level as f32 / FADE_LEVELS as f32;
+                        materials.add(ColorMaterial::from(rules.color.with_a(alpha)))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            collector_mesh,
+            collector_material: materials.add(ColorMaterial::from(Color::RED)),
+            enemy_mesh,
+            enemy_material: materials.add(ColorMaterial::from(Color::RED)),
+            projectile_mesh,
+            projectile_material: materials.add(ColorMaterial::from(Color::RED)),
+            building_mesh,
+            building_material: materials.add(ColorMaterial::from(Color::WHITE)),
+            debri_meshes,
+            debri_materials,
+        }
+    }
+
+    pub fn debri_mesh(&self, kind: DebriKind) -> Handle<Mesh> {
+        self.debri_meshes[kind as usize].clone()
+    }
+
+    /// Material of a `kind` debri drawn at `alpha`, rounded to the nearest
+    /// fade level.
+    pub fn debri_material(&self, kind: DebriKind, alpha: f32) -> &Handle<ColorMaterial> {
+        let level = (alpha.clamp(0.0, 1.0) * FADE_LEVELS as f32).round() as usize;
+        &self.debri_materials[kind as usize][level]
+    }
+}
+
+/// How the edges of the world treat whatever reaches them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundsMode {
+    /// Bounces off the edges, keeping `restitution` of the speed.
+    Bounce { restitution: f32 },
+    /// Leaving through one edge comes back in through the opposite one.
+    Wrap,
+    /// Within `margin` of an edge a push back in grows up to `strength` at
+    /// the edge itself.
+    Repel { margin: f32, strength: f32 },
+    /// Nothing holds them in, they are despawned once `margin` past the edges.
+    Open { margin: f32 },
+}
+
+impl BoundsMode {
+    /// The following mode, with its default settings.
+    pub fn next(self) -> Self {
+        match self {
+            BoundsMode::Bounce { .. } => BoundsMode::Wrap,
+            BoundsMode::Wrap => BoundsMode::Repel {
+                margin: 40.0,
+                strength: 600.0,
+            },
+            BoundsMode::Repel { .. } => BoundsMode::Open { margin: 200.0 },
+            BoundsMode::Open { .. } => BoundsMode::Bounce { restitution: 1.0 },
+        }
+    }
+}
+
+/// What `WorldBounds::apply` did to one position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundsHit {
+    Inside,
+    /// Put back on the edge. `normal` points inwards on every axis that was
+    /// crossed and is zero on the others.
+    Bounced {
+        normal: Vec3,
+    },
+    Wrapped,
+    /// Too close to an edge, `push` is the acceleration back in.
+    Repelled {
+        push: Vec3,
+    },
+    /// Gone for good, the caller despawns it.
+    Escaped,
+}
+
+/// Edge behaviour of the world shared by debri, collectors, projectiles and
+/// the player. The edges themselves are `DebriUniverse.bounds`.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct WorldBounds {
+    pub mode: BoundsMode,
+}
+
+impl Default for WorldBounds {
+    fn default() -> Self {
+        Self {
+            mode: BoundsMode::Bounce { restitution: 1.0 },
+        }
+    }
+}
+
+impl WorldBounds {
+    /// Holds a body of `radius` at `position` to `region`, moving it if the
+    /// mode says so.
+    pub fn apply(&self, region: &Region, position: &mut Vec3, radius: f32) -> BoundsHit {
+        match self.mode {
+            BoundsMode::Bounce { .. } => {
+                let mut normal = Vec3::ZERO;
+                for axis in 0..2 {
+                    // a body wider than the world sits in the middle
+                    let low = region.min[axis] + radius;
+                    let high = (region.max[axis] - radius).max(low);
+                    if position[axis] < low {
+                        position[axis] = low;
+                        normal[axis] = 1.0;
+                    } else if position[axis] > high {
+                        position[axis] = high;
+                        normal[axis] = -1.0;
+                    }
+                }
+                if normal == Vec3::ZERO {
+                    BoundsHit::Inside
+                } else {
+                    BoundsHit::Bounced { normal }
+                }
+            }
+            BoundsMode::Wrap => {
+                let mut wrapped = false;
+                for axis in 0..2 {
+                    let size = region.max[axis] - region.min[axis];
+                    if size > 0.0
+                        && (position[axis] < region.min[axis] || position[axis] >= region.max[axis])
+                    {
+                        position[axis] =
+                            region.min[axis] + (position[axis] - region.min[axis]).rem_euclid(size);
+                        wrapped = true;
+                    }
+                }
+                if wrapped {
+                    BoundsHit::Wrapped
+                } else {
+                    BoundsHit::Inside
+                }
+            }
+            BoundsMode::Repel { margin, strength } => {
+                let mut push = Vec3::ZERO;
+                for axis in 0..2 {
+                    let below = region.min[axis] + margin - (position[axis] - radius);
+                    let above = position[axis] + radius - (region.max[axis] - margin);
+                    // strongest at the edge and past it
+                    let ratio = |depth: f32| (depth / margin.max(f32::EPSILON)).min(1.0);
+                    if below > 0.0 {
+                        push[axis] += strength * ratio(below);
+                    }
+                    if above > 0.0 {
+                        push[axis] -= strength * ratio(above);
+                    }
+                }
+                if push == Vec3::ZERO {
+                    BoundsHit::Inside
+                } else {
+                    BoundsHit::Repelled { push }
+                }
+            }
+            BoundsMode::Open { margin } => {
+                let body = Region::from_center(position.truncate(), Vec2::splat(radius));
+                if region.with_margin(margin).intersects(&body) {
+                    BoundsHit::Inside
+                } else {
+                    BoundsHit::Escaped
+                }
+            }
+        }
+    }
+
+    /// Turns `hit` into a change of `velocity` over `delta_time` seconds.
+    pub fn deflect(&self, hit: BoundsHit, velocity: &mut Vec3, delta_time: f32) {
+        match (hit, self.mode) {
+            (BoundsHit::Bounced { normal }, BoundsMode::Bounce { restitution }) => {
+                for axis in 0..2 {
+                    // only turn around what still heads out
+                    if normal[axis] * velocity[axis] < 0.0 {
+                        velocity[axis] *= -restitution;
+                    }
+                }
+            }
+            (BoundsHit::Repelled { push }, _) => *velocity += push * delta_time,
+            _ => {}
+        }
+    }
+
+    /// Shortest offset from `from` to `to`, across the edges when they wrap.
+    pub fn offset(&self, region: &Region, from: Vec3, to: Vec3) -> Vec3 {
+        let mut offset = to - from;
+        if self.mode == BoundsMode::Wrap {
+            for axis in 0..2 {
+                let size = region.max[axis] - region.min[axis];
+                if size > 0.0 {
+                    offset[axis] -= size * (offset[axis] / size).round();
+                }
+            }
+        }
+        offset
+    }
+
+    /// Shifts of `center` to search the spatial index around, so a circle
+    /// reaching over a wrapping edge also finds what is on the other side.
+    /// A body found around `center + shift` is really at `position - shift`.
+    pub fn images(&self, region: &Region, center: Vec2, radius: f32) -> Vec<Vec2> {
+        if self.mode != BoundsMode::Wrap {
+            return vec![Vec2::ZERO];
+        }
+        let size = region.max - region.min;
+        let shifts = |axis: usize| {
+            let mut shifts = vec![0.0];
+            if center[axis] - radius < region.min[axis] {
+                shifts.push(size[axis]);
+            }
+            if center[axis] + radius > region.max[axis] {
+                shifts.push(-size[axis]);
+            }
+            shifts
+        };
+        let (xs, ys) = (shifts(0), shifts(1));
+        xs.iter()
+            .flat_map(|x| ys.iter().map(move |y| Vec2::new(*x, *y)))
+            .collect()
+    }
+}
+
+/// Set to `<width>x<height>` to pick the world size.
+pub const WORLD_SIZE_ENV: &str = "WORLD_SIZE";
+/// Set to `letterbox` to draw the world letterboxed.
+pub const WORLD_FIT_ENV: &str = "WORLD_FIT";
+
+/// How the camera shows the world in a window of another shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CameraFit {
+    /// Zooms so the whole world is in view, the rest of the window shows
+    /// what lies past the edges.
+    #[default]
+    Fit,
+    /// Like `Fit`, but only draws into the middle of the window, shaped like
+    /// the world.
+    Letterbox,
+}
+
+/// Size of the world, whatever the size of the window showing it.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct WorldConfig {
+    pub size: Vec2,
+    pub fit: CameraFit,
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self {
+            size: Vec2::new(1280.0, 720.0),
+            fit: CameraFit::default(),
+        }
+    }
+}
+
+impl WorldConfig {
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Some(size) = std::env::var(WORLD_SIZE_ENV)
+            .ok()
+            .and_then(|size| Self::parse_size(&size))
+        {
+            config.size = size;
+        }
+        if std::env::var(WORLD_FIT_ENV).is_ok_and(|fit| fit.eq_ignore_ascii_case("letterbox")) {
+            config.fit = CameraFit::Letterbox;
+        }
+        config
+    }
+
+    /// Reads `<width>x<height>`, both above zero.
+    pub fn parse_size(size: &str) -> Option<Vec2> {
+        let (width, height) = size.trim().split_once('x')?;
+        let size = Vec2::new(width.trim().parse().ok()?, height.trim().parse().ok()?);
+        (size.x > 0.0 && size.y > 0.0).then_some(size)
+    }
+
+    pub fn center(&self) -> Vec2 {
+        self.size / 2.0
+    }
+
+    /// Position and size of the largest world shaped area centered in a
+    /// window of `window` physical pixels.
+    pub fn letterbox(&self, window: UVec2) -> (UVec2, UVec2) {
+        let window_size = window.as_vec2();
+        let scale = (window_size / self.size).min_element();
+        let size = (self.size * scale)
+            .round()
+            .as_uvec2()
+            .min(window)
+            .max(UVec2::ONE);
+        ((window - size.min(window)) / 2, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world() -> Region {
+        Region::new(Vec2::ZERO, Vec2::new(100.0, 50.0))
+    }
+
+    #[test]
+    fn bounds_modes_hold_bodies_to_the_world() {
+        let mut position = Vec3::new(-5.0, 20.0, 0.0);
+        let bounce = WorldBounds::default();
+        let hit = bounce.apply(&world(), &mut position, 2.0);
+        assert_eq!(hit, BoundsHit::Bounced { normal: Vec3::X });
+        assert_eq!(position, Vec3::new(2.0, 20.0, 0.0));
+        let mut velocity = Vec3::new(-10.0, 3.0, 0.0);
+        bounce.deflect(hit, &mut velocity, 0.1);
+        assert_eq!(velocity, Vec3::new(10.0, 3.0, 0.0));
+
+        let wrap = WorldBounds {
+            mode: BoundsMode::Wrap,
+        };
+        let mut position = Vec3::new(105.0, -10.0, 0.0);
+        assert_eq!(wrap.apply(&world(), &mut position, 2.0), BoundsHit::Wrapped);
+        assert_eq!(position, Vec3::new(5.0, 40.0, 0.0));
+
+        let open = WorldBounds {
+            mode: BoundsMode::Open { margin: 10.0 },
+        };
+        let mut position = Vec3::new(-11.0, 20.0, 0.0);
+        assert_eq!(open.apply(&world(), &mut position, 2.0), BoundsHit::Inside);
+        position.x = -13.0;
+        assert_eq!(open.apply(&world(), &mut position, 2.0), BoundsHit::Escaped);
+    }
+
+    #[test]
+    fn wrapped_offsets_and_queries_cross_the_edges() {
+        let wrap = WorldBounds {
+            mode: BoundsMode::Wrap,
+        };
+        let offset = wrap.offset(
+            &world(),
+            Vec3::new(95.0, 5.0, 0.0),
+            Vec3::new(5.0, 45.0, 0.0),
+        );
+        assert_eq!(offset, Vec3::new(10.0, -10.0, 0.0));
+        assert_eq!(
+            wrap.images(&world(), Vec2::new(50.0, 25.0), 10.0),
+            vec![Vec2::ZERO]
+        );
+        assert_eq!(wrap.images(&world(), Vec2::new(95.0, 5.0), 10.0).len(), 4);
+        assert_eq!(
+            WorldBounds::default().images(&world(), Vec2::new(95.0, 5.0), 10.0),
+            vec![Vec2::ZERO]
+        );
+    }
+
+    #[test]
+    fn world_size_comes_from_the_config_not_the_window() {
+        assert_eq!(
+            WorldConfig::parse_size("1600x900"),
+            Some(Vec2::new(1600.0, 900.0))
+        );
+        assert_eq!(WorldConfig::parse_size("1600"), None);
+        assert_eq!(WorldConfig::parse_size("0x900"), None);
+
+        let config = WorldConfig::default();
+        // a wide window gets bars left and right, a tall one above and below
+        assert_eq!(
+            config.letterbox(UVec2::new(2000, 720)),
+            (UVec2::new(360, 0), UVec2::new(1280, 720))
+        );
+        assert_eq!(
+            config.letterbox(UVec2::new(640, 720)),
+            (UVec2::new(0, 180), UVec2::new(640, 360))
+        );
+    }
+}
@@ -1,21 +1,19 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 use rand::prelude::*;
 
 use super::components::Star;
 use super::resources::*;
 use super::NUMBER_OF_STARS;
+use crate::game::resources::WorldConfig;
 
 pub fn spawn_stars(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    config: Res<WorldConfig>,
     asset_server: Res<AssetServer>,
 ) {
-    let window = window_query.get_single().unwrap();
-
     for _ in 0..NUMBER_OF_STARS {
-        let random_x = random::<f32>() * window.width();
-        let random_y = random::<f32>() * window.height();
+        let random_x = random::<f32>() * config.size.x;
+        let random_y = random::<f32>() * config.size.y;
 
         commands.spawn((
             SpriteBundle {
@@ -40,14 +38,13 @@ pub fn tick_star_spawn_timer(mut star_spawn_timer: ResMut<StarSpawnTimer>, time:
 
 pub fn spawn_stars_over_time(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    config: Res<WorldConfig>,
     asset_server: Res<AssetServer>,
     star_spawn_timer: Res<StarSpawnTimer>,
 ) {
     if star_spawn_timer.timer.finished() {
-        let window = window_query.get_single().unwrap();
-        let random_x = random::<f32>() * window.width();
-        let random_y = random::<f32>() * window.height();
+        let random_x = random::<f32>() * config.size.x;
+        let random_y = random::<f32>() * config.size.y;
 
         commands.spawn((
             SpriteBundle {
@@ -1,3 +1,7 @@
+use crate::game::components::{Bounded, OrbitCenter};
+use crate::game::debri::components::Collider;
+use crate::game::debri::resources::{DebriKinds, DebriUniverse};
+use crate::game::resources::{BoundsHit, GameAssets, GameRng, WorldBounds};
 use crate::game::SimulationState;
 use bevy::prelude::*;
 
@@ -21,3 +25,64 @@ pub fn toggle_simulation(
         }
     }
 }
+
+/// Rewinds `GameRng` so the next game replays from the seed.
+pub fn reset_game_rng(mut rng: ResMut<GameRng>) {
+    rng.reset();
+}
+
+pub fn insert_game_assets(
+    mut commands: Commands,
+    kinds: Res<DebriKinds>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.insert_resource(GameAssets::new(&kinds, &mut meshes, &mut materials));
+}
+
+pub fn remove_game_assets(mut commands: Commands) {
+    commands.remove_resource::<GameAssets>();
+}
+
+/// Holds everything `Bounded` to the `WorldBounds`, despawning what is lost to
+/// open space. They steer themselves every tick, so a repulsion pushes them
+/// along at its strength instead of accelerating them. Orbiting bodies take
+/// their orbit along instead, keeping its radius, and are never despawned.
+pub fn keep_in_bounds(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Transform, Option<&Collider>), With<Bounded>>,
+    mut orbits: Query<&mut OrbitCenter>,
+    universe: Res<DebriUniverse>,
+    bounds: Res<WorldBounds>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, collider) in query.iter_mut() {
+        let mut position = transform.translation;
+        let radius = collider.map_or(0.0, |collider| collider.radius);
+        let orbit = orbits.get_mut(entity).ok();
+        match bounds.apply(&universe.bounds, &mut position, radius) {
+            BoundsHit::Inside => continue,
+            // the orbit brings it back around
+            BoundsHit::Escaped if orbit.is_some() => continue,
+            BoundsHit::Escaped => {
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+            BoundsHit::Repelled { push } => position += push * time.delta_seconds(),
+            BoundsHit::Bounced { .. } | BoundsHit::Wrapped => {}
+        }
+        if let Some(mut orbit) = orbit {
+            let shift = position - transform.translation;
+            orbit.x += shift.x;
+            orbit.y += shift.y;
+        }
+        transform.translation = position;
+    }
+}
+
+pub fn cycle_world_bounds(keyboard_input: Res<Input<KeyCode>>, mut bounds: ResMut<WorldBounds>) {
+    if keyboard_input.just_pressed(KeyCode::W) {
+        bounds.mode = bounds.mode.next();
+        println!("World bounds: {:?}", bounds.mode);
+    }
+}
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use super::components::*;
+use crate::game::components::GravityWell;
+use crate::game::resources::WorldConfig;
+
+pub fn spawn_target(
+    mut commands: Commands,
+    config: Res<WorldConfig>,
+    asset_server: Res<AssetServer>,
+) {
+    let center = config.center();
+
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_xyz(center.x, center.y, 0.0),
+            texture: asset_server.load("sprites/ball_white_large.png"),
+            ..default()
+        },
+        Target {},
+        GravityWell::default(),
+    ));
+}
+
+pub fn despawn_target(mut commands: Commands, target_query: Query<Entity, With<Target>>) {
+    for target_entity in target_query.iter() {
+        commands.entity(target_entity).despawn();
+    }
+}
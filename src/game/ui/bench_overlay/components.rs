@@ -0,0 +1,7 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct BenchOverlay {}
+
+#[derive(Component)]
+pub struct BenchText {}
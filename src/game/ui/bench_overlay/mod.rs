@@ -0,0 +1,27 @@
+mod components;
+mod styles;
+mod systems;
+
+use systems::interactions::*;
+use systems::layout::*;
+use systems::updates::*;
+
+use crate::AppState;
+use bevy::prelude::*;
+
+pub struct BenchOverlayPlugin;
+
+impl Plugin for BenchOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // OnEnter Systems
+            .add_systems(OnEnter(AppState::Game), spawn_bench_overlay)
+            // Systems
+            .add_systems(
+                Update,
+                (toggle_bench_overlay, update_bench_text).run_if(in_state(AppState::Game)),
+            )
+            // OnExit Systems
+            .add_systems(OnExit(AppState::Game), despawn_bench_overlay);
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+pub const BACKGROUND_COLOR: Color = Color::rgba(0.25, 0.25, 0.25, 0.5);
+
+pub const BENCH_OVERLAY_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.position_type = PositionType::Absolute; // Needed to display separately from HUD.
+    style.left = Val::Px(8.0);
+    style.bottom = Val::Px(8.0);
+    style.padding = UiRect::all(Val::Px(8.0));
+
+    style
+};
+
+pub fn get_bench_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 18.0,
+        color: Color::rgb(1.0, 1.0, 1.0),
+    }
+}
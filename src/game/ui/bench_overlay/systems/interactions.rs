@@ -0,0 +1,24 @@
+use bevy::prelude::*;
+
+use crate::game::debri::resources::QuadBench;
+use crate::game::ui::bench_overlay::components::BenchOverlay;
+use crate::game::ui::bench_overlay::systems::layout::build_bench_overlay;
+
+pub fn toggle_bench_overlay(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    mut bench: ResMut<QuadBench>,
+    overlay_query: Query<Entity, With<BenchOverlay>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F3) {
+        bench.show_overlay = !bench.show_overlay;
+        if bench.show_overlay {
+            build_bench_overlay(&mut commands, &asset_server);
+        } else {
+            for entity in overlay_query.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+use crate::game::debri::resources::QuadBench;
+use crate::game::ui::bench_overlay::components::*;
+use crate::game::ui::bench_overlay::styles::*;
+
+pub fn spawn_bench_overlay(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    bench: Res<QuadBench>,
+) {
+    if bench.show_overlay {
+        build_bench_overlay(&mut commands, &asset_server);
+    }
+}
+
+pub fn despawn_bench_overlay(
+    mut commands: Commands,
+    overlay_query: Query<Entity, With<BenchOverlay>>,
+) {
+    for entity in overlay_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn build_bench_overlay(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    let overlay_entity = commands
+        .spawn((
+            NodeBundle {
+                style: BENCH_OVERLAY_STYLE,
+                background_color: BACKGROUND_COLOR.into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            BenchOverlay {},
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    text: Text {
+                        sections: vec![TextSection::new("", get_bench_text_style(asset_server))],
+                        alignment: TextAlignment::Left,
+                        ..default()
+                    },
+                    ..default()
+                },
+                BenchText {},
+            ));
+        })
+        .id();
+
+    overlay_entity
+}
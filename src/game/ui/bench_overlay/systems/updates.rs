@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+use crate::game::debri::resources::{DebriUniverse, QuadBench};
+use crate::game::ui::bench_overlay::components::BenchText;
+
+pub fn update_bench_text(
+    bench: Res<QuadBench>,
+    universe: Res<DebriUniverse>,
+    mut text_query: Query<&mut Text, With<BenchText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!(
+            "{:?}\nbuild  avg {}us  p95 {}us\nquery  avg {}us  p95 {}us\nnodes {}  leaves {}  depth {}  values/leaf {:.1}",
+            universe.backend,
+            bench.avarage_build_time,
+            bench.p95_build_time,
+            bench.avarage_query_time,
+            bench.p95_query_time,
+            bench.stats.nodes,
+            bench.stats.leaves,
+            bench.stats.max_depth,
+            bench.stats.values_per_leaf(),
+        );
+    }
+}
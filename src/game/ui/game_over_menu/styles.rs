@@ -9,7 +9,7 @@ pub const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
 pub const GAME_OVER_MENU_STYLE: Style = {
     let mut style = Style::DEFAULT;
     style.position_type = PositionType::Absolute; // Needed to display separately from HUD.
-    style.display = Display::Flex;                // Hidden by Default
+    style.display = Display::Flex; // Hidden by Default
     style.justify_content = JustifyContent::Center;
     style.align_items = AlignItems::Center;
     style.width = Val::Percent(100.0);
@@ -3,13 +3,10 @@ use bevy::prelude::*;
 
 use crate::game::ui::game_over_menu::components::*;
 use crate::game::ui::game_over_menu::styles::*;
-use crate::AppState;
+use crate::{AppState, ButtonChanged, ButtonVisuals};
 
 pub fn interact_with_restart_button(
-    mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<RestartButton>),
-    >,
+    mut button_query: Query<ButtonVisuals, ButtonChanged<RestartButton>>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
 ) {
     for (interaction, mut color) in button_query.iter_mut() {
@@ -29,10 +26,7 @@ pub fn interact_with_restart_button(
 }
 
 pub fn interact_with_main_menu_button(
-    mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<MainMenuButton>),
-    >,
+    mut button_query: Query<ButtonVisuals, ButtonChanged<MainMenuButton>>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
 ) {
     for (interaction, mut color) in button_query.iter_mut() {
@@ -53,10 +47,7 @@ pub fn interact_with_main_menu_button(
 
 pub fn interact_with_quit_button(
     mut app_exit_event_writer: EventWriter<AppExit>,
-    mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<QuitButton>),
-    >,
+    mut button_query: Query<ButtonVisuals, ButtonChanged<QuitButton>>,
 ) {
     for (interaction, mut color) in button_query.iter_mut() {
         match *interaction {
@@ -30,7 +30,7 @@ pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetSer
                         text: Text {
                             sections: vec![TextSection::new(
                                 "Game Over",
-                                get_title_text_style(&asset_server),
+                                get_title_text_style(asset_server),
                             )],
                             alignment: TextAlignment::Center,
                             ..default()
@@ -43,7 +43,7 @@ pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetSer
                             text: Text {
                                 sections: vec![TextSection::new(
                                     "Your final score was:",
-                                    get_final_score_text_style(&asset_server),
+                                    get_final_score_text_style(asset_server),
                                 )],
                                 alignment: TextAlignment::Center,
                                 ..default()
@@ -68,7 +68,7 @@ pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetSer
                                 text: Text {
                                     sections: vec![TextSection::new(
                                         "Restart",
-                                        get_button_text_style(&asset_server),
+                                        get_button_text_style(asset_server),
                                     )],
                                     alignment: TextAlignment::Center,
                                     ..default()
@@ -92,7 +92,7 @@ pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetSer
                                 text: Text {
                                     sections: vec![TextSection::new(
                                         "Main Menu",
-                                        get_button_text_style(&asset_server),
+                                        get_button_text_style(asset_server),
                                     )],
                                     alignment: TextAlignment::Center,
                                     ..default()
@@ -116,7 +116,7 @@ pub fn build_game_over_menu(commands: &mut Commands, asset_server: &Res<AssetSer
                                 text: Text {
                                     sections: vec![TextSection::new(
                                         "Quit",
-                                        get_button_text_style(&asset_server),
+                                        get_button_text_style(asset_server),
                                     )],
                                     alignment: TextAlignment::Center,
                                     ..default()
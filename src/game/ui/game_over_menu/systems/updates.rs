@@ -9,7 +9,7 @@ pub fn update_final_score_text(
 ) {
     for event in game_over_event_reader.read() {
         for mut text in text_query.iter_mut() {
-            text.sections[0].value = format!("Final Score: {}", event.score.to_string());
+            text.sections[0].value = format!("Final Score: {}", event.score);
         }
     }
 }
@@ -1,7 +1,7 @@
 use bevy::prelude::Component;
 
 #[derive(Component)]
-pub struct HUD {}
+pub struct Hud {}
 
 #[derive(Component)]
 pub struct ScoreText {}
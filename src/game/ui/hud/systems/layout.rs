@@ -14,7 +14,7 @@ pub fn build_hud(commands: &mut Commands, asset_server: &Res<AssetServer>) -> En
                 style: HUD_STYLE,
                 ..default()
             },
-            HUD {},
+            Hud {},
         ))
         .with_children(|parent| {
             // LHS
@@ -36,10 +36,7 @@ pub fn build_hud(commands: &mut Commands, asset_server: &Res<AssetServer>) -> En
                         TextBundle {
                             style: Style { ..default() },
                             text: Text {
-                                sections: vec![TextSection::new(
-                                    "0",
-                                    get_text_style(&asset_server),
-                                )],
+                                sections: vec![TextSection::new("0", get_text_style(asset_server))],
                                 alignment: TextAlignment::Center,
                                 ..default()
                             },
@@ -61,10 +58,7 @@ pub fn build_hud(commands: &mut Commands, asset_server: &Res<AssetServer>) -> En
                         TextBundle {
                             style: Style { ..default() },
                             text: Text {
-                                sections: vec![TextSection::new(
-                                    "0",
-                                    get_text_style(&asset_server),
-                                )],
+                                sections: vec![TextSection::new("0", get_text_style(asset_server))],
                                 alignment: TextAlignment::Center,
                                 ..default()
                             },
@@ -85,7 +79,7 @@ pub fn build_hud(commands: &mut Commands, asset_server: &Res<AssetServer>) -> En
     hud_entity
 }
 
-pub fn despawn_hud(mut commands: Commands, hud_query: Query<Entity, With<HUD>>) {
+pub fn despawn_hud(mut commands: Commands, hud_query: Query<Entity, With<Hud>>) {
     for entity in hud_query.iter() {
         commands.entity(entity).despawn_recursive();
     }
@@ -1,25 +1,35 @@
+mod bench_overlay;
 mod game_over_menu;
 mod hud;
 mod pause_menu;
+pub mod resources;
 mod spawn_toolbar;
+mod tuning_panel;
 
+use bench_overlay::BenchOverlayPlugin;
 use bevy::prelude::*;
 use game_over_menu::GameOverMenuPlugin;
 use hud::HudPlugin;
 use pause_menu::PauseMenuPlugin;
+use resources::UiFocus;
 use spawn_toolbar::SpawnToolbarPlugin;
+use tuning_panel::TuningPanelPlugin;
 
 pub struct GameUIPlugin;
 
 impl Plugin for GameUIPlugin {
     fn build(&self, app: &mut App) {
         app
+            // Resources
+            .init_resource::<UiFocus>()
             // Plugins
             .add_plugins((
                 HudPlugin,
                 PauseMenuPlugin,
                 GameOverMenuPlugin,
                 SpawnToolbarPlugin,
+                BenchOverlayPlugin,
+                TuningPanelPlugin,
             ));
     }
 }
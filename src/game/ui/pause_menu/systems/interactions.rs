@@ -4,13 +4,10 @@ use bevy::prelude::*;
 use crate::game::ui::pause_menu::components::*;
 use crate::game::ui::pause_menu::styles::*;
 use crate::game::SimulationState;
-use crate::AppState;
+use crate::{AppState, ButtonChanged, ButtonVisuals};
 
 pub fn interact_with_resume_button(
-    mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<ResumeButton>),
-    >,
+    mut button_query: Query<ButtonVisuals, ButtonChanged<ResumeButton>>,
     mut simulation_state_next_state: ResMut<NextState<SimulationState>>,
 ) {
     for (interaction, mut color) in button_query.iter_mut() {
@@ -30,10 +27,7 @@ pub fn interact_with_resume_button(
 }
 
 pub fn interact_with_main_menu_button(
-    mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<MainMenuButton>),
-    >,
+    mut button_query: Query<ButtonVisuals, ButtonChanged<MainMenuButton>>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
 ) {
     for (interaction, mut color) in button_query.iter_mut() {
@@ -54,10 +48,7 @@ pub fn interact_with_main_menu_button(
 
 pub fn interact_with_quit_button(
     mut app_exit_event_writer: EventWriter<AppExit>,
-    mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<QuitButton>),
-    >,
+    mut button_query: Query<ButtonVisuals, ButtonChanged<QuitButton>>,
 ) {
     for (interaction, mut color) in button_query.iter_mut() {
         match *interaction {
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+#[derive(Resource, Default)]
+pub struct UiFocus {
+    /// Whether the cursor is over a panel, so the world shouldn't react to it.
+    pub pointer_over_ui: bool,
+}
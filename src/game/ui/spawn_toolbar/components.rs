@@ -2,6 +2,8 @@ use bevy::prelude::Component;
 
 pub enum BuldingType {
     Collector,
+    // no toolbar button builds one yet
+    #[allow(dead_code)]
     Shooter,
 }
 
@@ -2,9 +2,9 @@ mod components;
 mod styles;
 mod systems;
 
+use crate::AppState;
 use systems::interactions::*;
 use systems::layout::*;
-use crate::AppState;
 
 use bevy::prelude::*;
 
@@ -1,4 +1,3 @@
-use bevy::app::AppExit;
 use bevy::prelude::*;
 
 use crate::game::building::components::EventSpawnBuilding;
@@ -7,9 +6,7 @@ use crate::game::ui::spawn_toolbar::components::*;
 use crate::game::ui::spawn_toolbar::styles::HOVERED_BUTTON;
 use crate::game::ui::spawn_toolbar::styles::NORMAL_BUTTON;
 use crate::game::ui::spawn_toolbar::styles::PRESSED_BUTTON;
-use crate::game::ui::spawn_toolbar::styles::*;
-use crate::game::SimulationState;
-use crate::AppState;
+use crate::ButtonChanged;
 
 pub fn interact_with_button(
     mut events_spawn_collector: EventWriter<CollectorSpawnEvent>,
@@ -17,7 +14,7 @@ pub fn interact_with_button(
     mut events_spawn_building: EventWriter<EventSpawnBuilding>,
     mut button_query: Query<
         (&Interaction, &mut BackgroundColor, &DefaultButton),
-        (Changed<Interaction>, With<DefaultButton>),
+        ButtonChanged<DefaultButton>,
     >,
 ) {
     for (interaction, mut color, default_button) in button_query.iter_mut() {
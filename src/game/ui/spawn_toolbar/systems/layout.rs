@@ -50,7 +50,7 @@ pub fn build_spawn_toolbar(commands: &mut Commands, asset_server: &Res<AssetServ
                                 text: Text {
                                     sections: vec![TextSection::new(
                                         "Collector",
-                                        get_button_text_style(&asset_server),
+                                        get_button_text_style(asset_server),
                                     )],
                                     alignment: TextAlignment::Center,
                                     ..default()
@@ -74,7 +74,7 @@ pub fn build_spawn_toolbar(commands: &mut Commands, asset_server: &Res<AssetServ
                                 text: Text {
                                     sections: vec![TextSection::new(
                                         "Stash",
-                                        get_button_text_style(&asset_server),
+                                        get_button_text_style(asset_server),
                                     )],
                                     alignment: TextAlignment::Center,
                                     ..default()
@@ -105,7 +105,7 @@ pub fn build_spawn_toolbar(commands: &mut Commands, asset_server: &Res<AssetServ
                                 text: Text {
                                     sections: vec![TextSection::new(
                                         "Base",
-                                        get_button_text_style(&asset_server),
+                                        get_button_text_style(asset_server),
                                     )],
                                     alignment: TextAlignment::Center,
                                     ..default()
@@ -128,7 +128,7 @@ pub fn build_spawn_toolbar(commands: &mut Commands, asset_server: &Res<AssetServ
                                 text: Text {
                                     sections: vec![TextSection::new(
                                         "Shooter",
-                                        get_button_text_style(&asset_server),
+                                        get_button_text_style(asset_server),
                                     )],
                                     alignment: TextAlignment::Center,
                                     ..default()
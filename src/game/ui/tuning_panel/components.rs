@@ -0,0 +1,145 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct TuningPanel {}
+
+/// A parameter the tuning panel can change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TuningField {
+    Separation,
+    Cohesion,
+    Alignment,
+    Vision,
+    MaxForce,
+    MaxSpeed,
+    Speed,
+    CollectorVision,
+    Damping,
+    MinSpeed,
+    TickRate,
+    MergeChance,
+    MergeMaxMass,
+    Lifetime,
+}
+
+impl TuningField {
+    /// Fields of the selected flock's `FlockRules`.
+    pub const FLOCK: [TuningField; 6] = [
+        TuningField::Separation,
+        TuningField::Cohesion,
+        TuningField::Alignment,
+        TuningField::Vision,
+        TuningField::MaxForce,
+        TuningField::MaxSpeed,
+    ];
+
+    /// Fields shared by every debri.
+    pub const WORLD: [TuningField; 8] = [
+        TuningField::Speed,
+        TuningField::CollectorVision,
+        TuningField::Damping,
+        TuningField::MinSpeed,
+        TuningField::TickRate,
+        TuningField::MergeChance,
+        TuningField::MergeMaxMass,
+        TuningField::Lifetime,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TuningField::Separation => "Separation",
+            TuningField::Cohesion => "Cohesion",
+            TuningField::Alignment => "Alignment",
+            TuningField::Vision => "Vision",
+            TuningField::MaxForce => "Max force",
+            TuningField::MaxSpeed => "Max speed",
+            TuningField::Speed => "Sim speed",
+            TuningField::CollectorVision => "Collector vision",
+            TuningField::Damping => "Damping",
+            TuningField::MinSpeed => "Min speed",
+            TuningField::TickRate => "Tick rate",
+            TuningField::MergeChance => "Merge chance",
+            TuningField::MergeMaxMass => "Merge max mass",
+            TuningField::Lifetime => "Debri lifetime",
+        }
+    }
+
+    /// Range covered by the slider. Typed values may go past it.
+    pub fn range(&self) -> (f32, f32) {
+        match self {
+            TuningField::Separation | TuningField::Cohesion | TuningField::Alignment => (0.0, 5.0),
+            TuningField::Vision => (0.0, 100.0),
+            TuningField::MaxForce => (0.0, 1000.0),
+            TuningField::MaxSpeed => (10.0, 600.0),
+            TuningField::Speed => (0.0, 5.0),
+            TuningField::CollectorVision => (0.0, 2.0),
+            TuningField::Damping => (0.0, 200.0),
+            TuningField::MinSpeed => (0.0, 300.0),
+            TuningField::TickRate => (10.0, 240.0),
+            TuningField::MergeChance => (0.0, 1.0),
+            TuningField::MergeMaxMass => (1.0, 50.0),
+            TuningField::Lifetime => (0.0, 120.0),
+        }
+    }
+
+    /// Closest value the field accepts, or `None` when `value` is not finite.
+    pub fn clamp(&self, value: f32) -> Option<f32> {
+        if !value.is_finite() {
+            return None;
+        }
+        let value = value.max(0.0);
+        Some(match self {
+            TuningField::TickRate => value.max(1.0),
+            TuningField::MergeChance => value.min(1.0),
+            _ => value,
+        })
+    }
+
+    pub fn format(&self, value: f32) -> String {
+        match self {
+            TuningField::Separation
+            | TuningField::Cohesion
+            | TuningField::Alignment
+            | TuningField::Speed
+            | TuningField::CollectorVision
+            | TuningField::MergeChance => format!("{:.2}", value),
+            _ => format!("{:.0}", value),
+        }
+    }
+}
+
+/// The bar dragged to set `field`.
+#[derive(Component)]
+pub struct TuningSlider {
+    pub field: TuningField,
+}
+
+#[derive(Component)]
+pub struct TuningSliderFill {
+    pub field: TuningField,
+}
+
+/// Clicked to type a value for `field`.
+#[derive(Component)]
+pub struct TuningInput {
+    pub field: TuningField,
+}
+
+#[derive(Component)]
+pub struct TuningInputText {
+    pub field: TuningField,
+}
+
+#[derive(Component)]
+pub enum TuningButton {
+    PreviousFlock,
+    NextFlock,
+    Save,
+    Load,
+}
+
+#[derive(Component)]
+pub struct FlockText {}
+
+#[derive(Component)]
+pub struct TuningStatusText {}
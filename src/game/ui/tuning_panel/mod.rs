@@ -0,0 +1,47 @@
+mod components;
+mod preset;
+mod resources;
+mod styles;
+mod systems;
+mod tunables;
+
+use resources::TuningPanelState;
+use systems::interactions::*;
+use systems::layout::*;
+use systems::updates::*;
+
+use crate::AppState;
+use bevy::prelude::*;
+
+/// Where the panel saves and loads its preset.
+pub const TUNING_PRESET_PATH: &str = "presets/tuning.cfg";
+
+pub struct TuningPanelPlugin;
+
+impl Plugin for TuningPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            // Resources
+            .init_resource::<TuningPanelState>()
+            // OnEnter Systems
+            .add_systems(OnEnter(AppState::Game), spawn_tuning_panel)
+            // Systems
+            .add_systems(
+                Update,
+                (
+                    toggle_tuning_panel,
+                    interact_with_tuning_buttons,
+                    drag_tuning_sliders,
+                    interact_with_tuning_inputs,
+                    type_tuning_input,
+                    block_camera_under_panel,
+                    update_tuning_sliders,
+                    update_tuning_inputs,
+                    update_tuning_labels,
+                )
+                    .run_if(in_state(AppState::Game)),
+            )
+            // OnExit Systems
+            .add_systems(OnExit(AppState::Game), despawn_tuning_panel);
+    }
+}
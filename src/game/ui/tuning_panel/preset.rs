@@ -0,0 +1,296 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::game::debri::resources::{
+    DebriDecay, DebriMerging, DebriUniverse, FlockParams, FlockRules, PhysicsTickRate,
+};
+use crate::game::ui::tuning_panel::components::TuningField;
+
+/// The tunable parameters, saved as `key = value` lines. Flock rules are keyed
+/// `flock.<index>.<rule>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuningPreset {
+    pub speed: f32,
+    pub vision: f32,
+    pub damping: f32,
+    pub min_speed: f32,
+    pub tick_rate: f32,
+    pub merge_chance: f32,
+    pub merge_max_mass: f32,
+    pub lifetime: f32,
+    pub flocks: Vec<FlockRules>,
+}
+
+impl TuningPreset {
+    pub fn capture(
+        universe: &DebriUniverse,
+        params: &FlockParams,
+        tick_rate: &PhysicsTickRate,
+        merging: &DebriMerging,
+        decay: &DebriDecay,
+    ) -> Self {
+        Self {
+            speed: universe.speed,
+            vision: universe.vision,
+            damping: universe.damping,
+            min_speed: universe.min_speed,
+            tick_rate: tick_rate.0,
+            merge_chance: merging.chance,
+            merge_max_mass: merging.max_mass,
+            lifetime: decay.lifetime,
+            flocks: params.flocks.clone(),
+        }
+    }
+
+    pub fn apply(
+        &self,
+        universe: &mut DebriUniverse,
+        params: &mut FlockParams,
+        tick_rate: &mut PhysicsTickRate,
+        merging: &mut DebriMerging,
+        decay: &mut DebriDecay,
+    ) {
+        // values from a file go through the same checks as the panel's inputs
+        let checked =
+            |field: TuningField, value: f32, current: f32| field.clamp(value).unwrap_or(current);
+        universe.speed = checked(TuningField::Speed, self.speed, universe.speed);
+        universe.vision = checked(TuningField::CollectorVision, self.vision, universe.vision);
+        universe.damping = checked(TuningField::Damping, self.damping, universe.damping);
+        universe.min_speed = checked(TuningField::MinSpeed, self.min_speed, universe.min_speed);
+        tick_rate.0 = checked(TuningField::TickRate, self.tick_rate, tick_rate.0);
+        merging.chance = checked(TuningField::MergeChance, self.merge_chance, merging.chance);
+        merging.max_mass = checked(
+            TuningField::MergeMaxMass,
+            self.merge_max_mass,
+            merging.max_mass,
+        );
+        decay.lifetime = checked(TuningField::Lifetime, self.lifetime, decay.lifetime);
+        if self.flocks.is_empty() {
+            return;
+        }
+        params
+            .flocks
+            .resize(self.flocks.len(), FlockRules::default());
+        for (loaded, rules) in self.flocks.iter().zip(params.flocks.iter_mut()) {
+            rules.separation =
+                checked(TuningField::Separation, loaded.separation, rules.separation);
+            rules.cohesion = checked(TuningField::Cohesion, loaded.cohesion, rules.cohesion);
+            rules.alignment = checked(TuningField::Alignment, loaded.alignment, rules.alignment);
+            rules.vision = checked(TuningField::Vision, loaded.vision, rules.vision);
+            rules.max_force = checked(TuningField::MaxForce, loaded.max_force, rules.max_force);
+            rules.max_speed = checked(TuningField::MaxSpeed, loaded.max_speed, rules.max_speed);
+            // not on the panel, but held to the same rule
+            if loaded.personal_space.is_finite() {
+                rules.personal_space = loaded.personal_space.max(0.0);
+            }
+            if loaded.spawn_weight.is_finite() {
+                rules.spawn_weight = loaded.spawn_weight.max(0.0);
+            }
+        }
+    }
+
+    pub fn write<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(out, "speed = {}", self.speed)?;
+        writeln!(out, "vision = {}", self.vision)?;
+        writeln!(out, "damping = {}", self.damping)?;
+        writeln!(out, "min_speed = {}", self.min_speed)?;
+        writeln!(out, "tick_rate = {}", self.tick_rate)?;
+        writeln!(out, "merge_chance = {}", self.merge_chance)?;
+        writeln!(out, "merge_max_mass = {}", self.merge_max_mass)?;
+        writeln!(out, "lifetime = {}", self.lifetime)?;
+        for (index, rules) in self.flocks.iter().enumerate() {
+            writeln!(out, "flock.{}.separation = {}", index, rules.separation)?;
+            writeln!(out, "flock.{}.cohesion = {}", index, rules.cohesion)?;
+            writeln!(out, "flock.{}.alignment = {}", index, rules.alignment)?;
+            writeln!(out, "flock.{}.vision = {}", index, rules.vision)?;
+            writeln!(
+                out,
+                "flock.{}.personal_space = {}",
+                index, rules.personal_space
+            )?;
+            writeln!(out, "flock.{}.max_force = {}", index, rules.max_force)?;
+            writeln!(out, "flock.{}.max_speed = {}", index, rules.max_speed)?;
+            writeln!(out, "flock.{}.spawn_weight = {}", index, rules.spawn_weight)?;
+        }
+        out.flush()
+    }
+
+    /// Overwrites the fields named in `input`, leaving the others as they are.
+    /// Blank lines and lines starting with `#` are skipped.
+    pub fn read<R: BufRead>(&mut self, input: R) -> io::Result<()> {
+        for (number, line) in input.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |reason: &str| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {}", number + 1, reason),
+                )
+            };
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| invalid("expected `key = value`"))?;
+            let key = key.trim();
+            let value: f32 = value
+                .trim()
+                .parse()
+                .map_err(|_| invalid("value is not a number"))?;
+            if !value.is_finite() {
+                return Err(invalid("value is not finite"));
+            }
+
+            let field = match key.split('.').collect::<Vec<_>>()[..] {
+                ["speed"] => &mut self.speed,
+                ["vision"] => &mut self.vision,
+                ["damping"] => &mut self.damping,
+                ["min_speed"] => &mut self.min_speed,
+                ["tick_rate"] => &mut self.tick_rate,
+                ["merge_chance"] => &mut self.merge_chance,
+                ["merge_max_mass"] => &mut self.merge_max_mass,
+                ["lifetime"] => &mut self.lifetime,
+                ["flock", index, rule] => {
+                    let index: usize = index.parse().map_err(|_| invalid("bad flock index"))?;
+                    // flocks are added one at a time, so a typo can't allocate thousands
+                    if index > self.flocks.len() {
+                        return Err(invalid("flock index skips ahead"));
+                    }
+                    if index == self.flocks.len() {
+                        self.flocks.push(FlockRules::default());
+                    }
+                    let rules = &mut self.flocks[index];
+                    match rule {
+                        "separation" => &mut rules.separation,
+                        "cohesion" => &mut rules.cohesion,
+                        "alignment" => &mut rules.alignment,
+                        "vision" => &mut rules.vision,
+                        "personal_space" => &mut rules.personal_space,
+                        "max_force" => &mut rules.max_force,
+                        "max_speed" => &mut rules.max_speed,
+                        "spawn_weight" => &mut rules.spawn_weight,
+                        _ => return Err(invalid("unknown flock rule")),
+                    }
+                }
+                _ => return Err(invalid("unknown key")),
+            };
+            *field = value;
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        self.write(BufWriter::new(File::create(path)?))
+    }
+
+    pub fn load(&mut self, path: &Path) -> io::Result<()> {
+        self.read(BufReader::new(File::open(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::Vec2;
+
+    use super::*;
+
+    #[test]
+    fn preset_survives_a_round_trip() {
+        let mut preset = TuningPreset {
+            speed: 1.5,
+            vision: 0.25,
+            damping: 30.0,
+            min_speed: 12.5,
+            tick_rate: 60.0,
+            merge_chance: 0.05,
+            merge_max_mass: 8.0,
+            lifetime: 45.0,
+            flocks: FlockParams::default().flocks,
+        };
+        preset.flocks[1].cohesion = 0.125;
+
+        let mut text = Vec::new();
+        preset.write(&mut text).unwrap();
+
+        let mut loaded = TuningPreset {
+            flocks: Vec::new(),
+            ..preset.clone()
+        };
+        loaded.speed = 0.0;
+        loaded.read(text.as_slice()).unwrap();
+        assert_eq!(loaded, preset);
+    }
+
+    #[test]
+    fn bad_lines_are_reported_with_their_number() {
+        let mut preset = TuningPreset {
+            speed: 1.0,
+            vision: 1.0,
+            damping: 50.0,
+            min_speed: 50.0,
+            tick_rate: 90.0,
+            merge_chance: 0.0,
+            merge_max_mass: 12.0,
+            lifetime: 0.0,
+            flocks: Vec::new(),
+        };
+        let err = preset
+            .read("# comment\nspeed = 2\nflock.0.wobble = 1\n".as_bytes())
+            .unwrap_err();
+        assert!(err.to_string().starts_with("line 3"));
+        assert_eq!(preset.speed, 2.0);
+    }
+
+    #[test]
+    fn loaded_values_are_checked_like_typed_ones() {
+        let mut preset = TuningPreset::capture(
+            &DebriUniverse::new(Vec2::ZERO, Vec2::splat(100.0)),
+            &FlockParams::default(),
+            &PhysicsTickRate::default(),
+            &DebriMerging::default(),
+            &DebriDecay::default(),
+        );
+        let flocks = preset.flocks.len();
+        assert!(preset.read("speed = inf\n".as_bytes()).is_err());
+        assert!(preset
+            .read("flock.4000000000.cohesion = 1\n".as_bytes())
+            .is_err());
+        preset
+            .read(format!("flock.{}.cohesion = 2\n", flocks).as_bytes())
+            .unwrap();
+        assert_eq!(preset.flocks.len(), flocks + 1);
+
+        preset.speed = -3.0;
+        preset.damping = f32::NAN;
+        preset.tick_rate = 0.0;
+        preset.merge_chance = 5.0;
+        preset.flocks[0].spawn_weight = -1.0;
+
+        let mut universe = DebriUniverse::new(Vec2::ZERO, Vec2::splat(100.0));
+        let mut params = FlockParams::default();
+        let mut tick_rate = PhysicsTickRate::default();
+        let mut merging = DebriMerging::default();
+        let mut decay = DebriDecay::default();
+        let damping = universe.damping;
+        preset.apply(
+            &mut universe,
+            &mut params,
+            &mut tick_rate,
+            &mut merging,
+            &mut decay,
+        );
+
+        assert_eq!(universe.speed, 0.0);
+        assert_eq!(universe.damping, damping);
+        assert_eq!(tick_rate.0, 1.0);
+        assert_eq!(merging.chance, 1.0);
+        assert_eq!(params.flocks.len(), flocks + 1);
+        assert_eq!(params.flocks[0].spawn_weight, 0.0);
+        assert_eq!(params.flocks[flocks].cohesion, 2.0);
+    }
+}
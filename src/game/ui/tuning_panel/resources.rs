@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::game::ui::tuning_panel::components::TuningField;
+
+#[derive(Resource, Default)]
+pub struct TuningPanelState {
+    pub show: bool,
+    /// Index of the flock whose rules the panel edits.
+    pub flock: usize,
+    /// Field being typed into, and what was typed so far.
+    pub editing: Option<TuningField>,
+    pub input: String,
+    /// Outcome of the last save or load.
+    pub status: String,
+}
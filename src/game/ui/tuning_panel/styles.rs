@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+
+pub const BACKGROUND_COLOR: Color = Color::rgba(0.1, 0.1, 0.1, 0.8);
+pub const SLIDER_COLOR: Color = Color::rgb(0.25, 0.25, 0.25);
+pub const SLIDER_FILL_COLOR: Color = Color::rgb(0.35, 0.75, 0.35);
+
+pub const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+pub const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+pub const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+pub const EDITING_INPUT: Color = Color::rgb(0.2, 0.3, 0.5);
+
+pub const TUNING_PANEL_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.position_type = PositionType::Absolute; // Needed to display separately from HUD.
+    style.flex_direction = FlexDirection::Column;
+    style.right = Val::Px(8.0);
+    style.top = Val::Px(8.0);
+    style.padding = UiRect::all(Val::Px(8.0));
+
+    style
+};
+
+pub const ROW_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.flex_direction = FlexDirection::Row;
+    style.align_items = AlignItems::Center;
+    style.margin = UiRect::new(Val::Px(0.0), Val::Px(0.0), Val::Px(2.0), Val::Px(2.0));
+
+    style
+};
+
+pub const LABEL_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.width = Val::Px(120.0);
+
+    style
+};
+
+pub const SLIDER_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.width = Val::Px(140.0);
+    style.height = Val::Px(12.0);
+    style.margin = UiRect::new(Val::Px(8.0), Val::Px(8.0), Val::Px(0.0), Val::Px(0.0));
+
+    style
+};
+
+pub const SLIDER_FILL_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.height = Val::Percent(100.0);
+    style.width = Val::Percent(0.0);
+
+    style
+};
+
+pub const INPUT_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.width = Val::Px(64.0);
+    style.height = Val::Px(20.0);
+
+    style
+};
+
+pub const BUTTON_STYLE: Style = {
+    let mut style = Style::DEFAULT;
+    style.justify_content = JustifyContent::Center;
+    style.align_items = AlignItems::Center;
+    style.height = Val::Px(24.0);
+    style.min_width = Val::Px(24.0);
+    style.padding = UiRect::new(Val::Px(8.0), Val::Px(8.0), Val::Px(0.0), Val::Px(0.0));
+    style.margin = UiRect::new(Val::Px(4.0), Val::Px(4.0), Val::Px(0.0), Val::Px(0.0));
+
+    style
+};
+
+pub fn get_tuning_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 16.0,
+        color: Color::rgb(1.0, 1.0, 1.0),
+    }
+}
@@ -0,0 +1,164 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use bevy::window::ReceivedCharacter;
+use bevy_pancam::PanCam;
+
+use crate::game::ui::resources::UiFocus;
+use crate::game::ui::tuning_panel::components::*;
+use crate::game::ui::tuning_panel::resources::TuningPanelState;
+use crate::game::ui::tuning_panel::styles::*;
+use crate::game::ui::tuning_panel::systems::layout::build_tuning_panel;
+use crate::game::ui::tuning_panel::tunables::Tunables;
+use crate::game::ui::tuning_panel::TUNING_PRESET_PATH;
+
+pub fn toggle_tuning_panel(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    mut state: ResMut<TuningPanelState>,
+    panel_query: Query<Entity, With<TuningPanel>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F9) {
+        state.show = !state.show;
+        state.editing = None;
+        if state.show {
+            build_tuning_panel(&mut commands, &asset_server);
+        } else {
+            for entity in panel_query.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+pub fn interact_with_tuning_buttons(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor, &TuningButton),
+        Changed<Interaction>,
+    >,
+    mut state: ResMut<TuningPanelState>,
+    mut tunables: Tunables,
+) {
+    for (interaction, mut background_color, button) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *background_color = PRESSED_BUTTON.into();
+                match button {
+                    TuningButton::PreviousFlock => {
+                        let count = tunables.flock_count();
+                        state.flock = (state.flock + count - 1) % count;
+                    }
+                    TuningButton::NextFlock => {
+                        state.flock = (state.flock + 1) % tunables.flock_count();
+                    }
+                    TuningButton::Save => {
+                        state.status = match tunables.preset().save(Path::new(TUNING_PRESET_PATH)) {
+                            Ok(()) => format!("Saved {}", TUNING_PRESET_PATH),
+                            Err(err) => format!("Could not save: {}", err),
+                        };
+                        println!("{}", state.status);
+                    }
+                    TuningButton::Load => {
+                        let mut preset = tunables.preset();
+                        state.status = match preset.load(Path::new(TUNING_PRESET_PATH)) {
+                            Ok(()) => {
+                                tunables.apply_preset(&preset);
+                                state.flock = state.flock.min(tunables.flock_count() - 1);
+                                format!("Loaded {}", TUNING_PRESET_PATH)
+                            }
+                            Err(err) => format!("Could not load: {}", err),
+                        };
+                        println!("{}", state.status);
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
+
+pub fn drag_tuning_sliders(
+    slider_query: Query<(&Interaction, &RelativeCursorPosition, &TuningSlider)>,
+    state: Res<TuningPanelState>,
+    mut tunables: Tunables,
+) {
+    for (interaction, cursor, slider) in slider_query.iter() {
+        // stays pressed while the button is held, even off the slider
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if let Some(position) = cursor.normalized {
+            let (min, max) = slider.field.range();
+            let value = min + position.x.clamp(0.0, 1.0) * (max - min);
+            tunables.set(slider.field, state.flock, value);
+        }
+    }
+}
+
+pub fn interact_with_tuning_inputs(
+    input_query: Query<(&Interaction, &TuningInput), Changed<Interaction>>,
+    mut state: ResMut<TuningPanelState>,
+) {
+    for (interaction, input) in input_query.iter() {
+        if *interaction == Interaction::Pressed {
+            // clicking the field being edited again cancels the edit
+            state.editing = match state.editing {
+                Some(field) if field == input.field => None,
+                _ => Some(input.field),
+            };
+            state.input.clear();
+        }
+    }
+}
+
+/// Typing into a value field. Enter applies the value, Backspace deletes.
+pub fn type_tuning_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut state: ResMut<TuningPanelState>,
+    mut tunables: Tunables,
+) {
+    let Some(field) = state.editing else {
+        characters.clear();
+        return;
+    };
+    for event in characters.read() {
+        if event.char.is_ascii_digit() || event.char == '.' || event.char == '-' {
+            state.input.push(event.char);
+        }
+    }
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        state.input.pop();
+    }
+    if keyboard_input.just_pressed(KeyCode::Return) {
+        if let Ok(value) = state.input.parse::<f32>() {
+            let flock = state.flock;
+            tunables.set(field, flock, value);
+        }
+        state.editing = None;
+        state.input.clear();
+    }
+}
+
+/// PanCam pans on any mouse drag, so it is switched off while the cursor is
+/// over the panel.
+pub fn block_camera_under_panel(
+    panel_query: Query<&RelativeCursorPosition, With<TuningPanel>>,
+    mut camera_query: Query<&mut PanCam>,
+    mut focus: ResMut<UiFocus>,
+) {
+    let over_panel = panel_query.iter().any(|cursor| cursor.mouse_over());
+    if focus.pointer_over_ui != over_panel {
+        focus.pointer_over_ui = over_panel;
+        for mut camera in camera_query.iter_mut() {
+            camera.enabled = !over_panel;
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+
+use crate::game::ui::tuning_panel::components::*;
+use crate::game::ui::tuning_panel::resources::TuningPanelState;
+use crate::game::ui::tuning_panel::styles::*;
+
+pub fn spawn_tuning_panel(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    state: Res<TuningPanelState>,
+) {
+    if state.show {
+        build_tuning_panel(&mut commands, &asset_server);
+    }
+}
+
+pub fn despawn_tuning_panel(
+    mut commands: Commands,
+    panel_query: Query<Entity, With<TuningPanel>>,
+    mut state: ResMut<TuningPanelState>,
+) {
+    for entity in panel_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    state.editing = None;
+}
+
+pub fn build_tuning_panel(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    let panel_entity = commands
+        .spawn((
+            NodeBundle {
+                style: TUNING_PANEL_STYLE,
+                background_color: BACKGROUND_COLOR.into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            RelativeCursorPosition::default(),
+            TuningPanel {},
+        ))
+        .with_children(|parent| {
+            // === Flock picker ===
+            parent
+                .spawn(NodeBundle {
+                    style: ROW_STYLE,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    build_button(parent, asset_server, "<", TuningButton::PreviousFlock);
+                    parent.spawn((
+                        TextBundle::from_section("", get_tuning_text_style(asset_server)),
+                        FlockText {},
+                    ));
+                    build_button(parent, asset_server, ">", TuningButton::NextFlock);
+                });
+            for field in TuningField::FLOCK {
+                build_field_row(parent, asset_server, field);
+            }
+
+            // === Shared by every debri ===
+            for field in TuningField::WORLD {
+                build_field_row(parent, asset_server, field);
+            }
+
+            // === Presets ===
+            parent
+                .spawn(NodeBundle {
+                    style: ROW_STYLE,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    build_button(parent, asset_server, "Save", TuningButton::Save);
+                    build_button(parent, asset_server, "Load", TuningButton::Load);
+                    parent.spawn((
+                        TextBundle::from_section("", get_tuning_text_style(asset_server)),
+                        TuningStatusText {},
+                    ));
+                });
+        })
+        .id();
+
+    panel_entity
+}
+
+fn build_field_row(parent: &mut ChildBuilder, asset_server: &Res<AssetServer>, field: TuningField) {
+    parent
+        .spawn(NodeBundle {
+            style: ROW_STYLE,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: LABEL_STYLE,
+                text: Text::from_section(field.label(), get_tuning_text_style(asset_server)),
+                ..default()
+            });
+            // === Slider ===
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: SLIDER_STYLE,
+                        background_color: SLIDER_COLOR.into(),
+                        ..default()
+                    },
+                    RelativeCursorPosition::default(),
+                    TuningSlider { field },
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: SLIDER_FILL_STYLE,
+                            background_color: SLIDER_FILL_COLOR.into(),
+                            ..default()
+                        },
+                        TuningSliderFill { field },
+                    ));
+                });
+            // === Typed value ===
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: INPUT_STYLE,
+                        background_color: NORMAL_BUTTON.into(),
+                        ..default()
+                    },
+                    TuningInput { field },
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section("", get_tuning_text_style(asset_server)),
+                        TuningInputText { field },
+                    ));
+                });
+        });
+}
+
+fn build_button(
+    parent: &mut ChildBuilder,
+    asset_server: &Res<AssetServer>,
+    label: &str,
+    button: TuningButton,
+) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: BUTTON_STYLE,
+                background_color: NORMAL_BUTTON.into(),
+                ..default()
+            },
+            button,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                get_tuning_text_style(asset_server),
+            ));
+        });
+}
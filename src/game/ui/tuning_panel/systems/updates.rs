@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use crate::game::ui::tuning_panel::components::*;
+use crate::game::ui::tuning_panel::resources::TuningPanelState;
+use crate::game::ui::tuning_panel::styles::*;
+use crate::game::ui::tuning_panel::tunables::TunableValues;
+
+pub fn update_tuning_sliders(
+    values: TunableValues,
+    state: Res<TuningPanelState>,
+    mut fill_query: Query<(&mut Style, &TuningSliderFill)>,
+) {
+    for (mut style, fill) in fill_query.iter_mut() {
+        let (min, max) = fill.field.range();
+        let value = values.get(fill.field, state.flock);
+        let ratio = ((value - min) / (max - min)).clamp(0.0, 1.0);
+        style.width = Val::Percent(ratio * 100.0);
+    }
+}
+
+pub fn update_tuning_inputs(
+    values: TunableValues,
+    state: Res<TuningPanelState>,
+    mut input_query: Query<(&mut BackgroundColor, &TuningInput), Without<TuningButton>>,
+    mut text_query: Query<(&mut Text, &TuningInputText)>,
+) {
+    for (mut background_color, input) in input_query.iter_mut() {
+        let color = if state.editing == Some(input.field) {
+            EDITING_INPUT
+        } else {
+            NORMAL_BUTTON
+        };
+        if background_color.0 != color {
+            *background_color = color.into();
+        }
+    }
+    for (mut text, input) in text_query.iter_mut() {
+        text.sections[0].value = if state.editing == Some(input.field) {
+            format!("{}_", state.input)
+        } else {
+            let value = values.get(input.field, state.flock);
+            input.field.format(value)
+        };
+    }
+}
+
+pub fn update_tuning_labels(
+    values: TunableValues,
+    state: Res<TuningPanelState>,
+    mut flock_query: Query<&mut Text, (With<FlockText>, Without<TuningStatusText>)>,
+    mut status_query: Query<&mut Text, (With<TuningStatusText>, Without<FlockText>)>,
+) {
+    for mut text in flock_query.iter_mut() {
+        text.sections[0].value = format!("Flock {}/{}", state.flock + 1, values.flock_count());
+    }
+    for mut text in status_query.iter_mut() {
+        text.sections[0].value = state.status.clone();
+    }
+}
@@ -0,0 +1,157 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::game::components::Velocity;
+use crate::game::debri::components::Debri;
+use crate::game::debri::resources::{
+    DebriDecay, DebriMerging, DebriUniverse, FlockParams, PhysicsTickRate,
+};
+use crate::game::ui::tuning_panel::components::TuningField;
+use crate::game::ui::tuning_panel::preset::TuningPreset;
+
+// Borrowed view of every resource a field can live in.
+struct Sources<'a> {
+    universe: &'a DebriUniverse,
+    params: &'a FlockParams,
+    tick_rate: &'a PhysicsTickRate,
+    merging: &'a DebriMerging,
+    decay: &'a DebriDecay,
+}
+
+impl Sources<'_> {
+    fn get(&self, field: TuningField, flock: usize) -> f32 {
+        let rules = &self.params.flocks[flock.min(self.params.flocks.len() - 1)];
+        match field {
+            TuningField::Separation => rules.separation,
+            TuningField::Cohesion => rules.cohesion,
+            TuningField::Alignment => rules.alignment,
+            TuningField::Vision => rules.vision,
+            TuningField::MaxForce => rules.max_force,
+            TuningField::MaxSpeed => rules.max_speed,
+            TuningField::Speed => self.universe.speed,
+            TuningField::CollectorVision => self.universe.vision,
+            TuningField::Damping => self.universe.damping,
+            TuningField::MinSpeed => self.universe.min_speed,
+            TuningField::TickRate => self.tick_rate.0,
+            TuningField::MergeChance => self.merging.chance,
+            TuningField::MergeMaxMass => self.merging.max_mass,
+            TuningField::Lifetime => self.decay.lifetime,
+        }
+    }
+}
+
+/// Everything the tuning panel shows, read only.
+#[derive(SystemParam)]
+pub struct TunableValues<'w> {
+    universe: Res<'w, DebriUniverse>,
+    params: Res<'w, FlockParams>,
+    tick_rate: Res<'w, PhysicsTickRate>,
+    merging: Res<'w, DebriMerging>,
+    decay: Res<'w, DebriDecay>,
+}
+
+impl<'w> TunableValues<'w> {
+    pub fn get(&self, field: TuningField, flock: usize) -> f32 {
+        Sources {
+            universe: &self.universe,
+            params: &self.params,
+            tick_rate: &self.tick_rate,
+            merging: &self.merging,
+            decay: &self.decay,
+        }
+        .get(field, flock)
+    }
+
+    pub fn flock_count(&self) -> usize {
+        self.params.flocks.len()
+    }
+}
+
+/// Everything the tuning panel writes to.
+#[derive(SystemParam)]
+pub struct Tunables<'w, 's> {
+    universe: ResMut<'w, DebriUniverse>,
+    params: ResMut<'w, FlockParams>,
+    tick_rate: ResMut<'w, PhysicsTickRate>,
+    merging: ResMut<'w, DebriMerging>,
+    decay: ResMut<'w, DebriDecay>,
+    debri_query: Query<'w, 's, &'static mut Velocity, With<Debri>>,
+}
+
+impl<'w, 's> Tunables<'w, 's> {
+    pub fn get(&self, field: TuningField, flock: usize) -> f32 {
+        Sources {
+            universe: &self.universe,
+            params: &self.params,
+            tick_rate: &self.tick_rate,
+            merging: &self.merging,
+            decay: &self.decay,
+        }
+        .get(field, flock)
+    }
+
+    pub fn flock_count(&self) -> usize {
+        self.params.flocks.len()
+    }
+
+    pub fn set(&mut self, field: TuningField, flock: usize, value: f32) {
+        if self.get(field, flock) == value {
+            return;
+        }
+        let Some(value) = field.clamp(value) else {
+            return;
+        };
+        let last = self.params.flocks.len() - 1;
+        match field {
+            TuningField::Separation => self.params.flocks[flock.min(last)].separation = value,
+            TuningField::Cohesion => self.params.flocks[flock.min(last)].cohesion = value,
+            TuningField::Alignment => self.params.flocks[flock.min(last)].alignment = value,
+            TuningField::Vision => self.params.flocks[flock.min(last)].vision = value,
+            TuningField::MaxForce => self.params.flocks[flock.min(last)].max_force = value,
+            TuningField::MaxSpeed => self.params.flocks[flock.min(last)].max_speed = value,
+            TuningField::Speed => self.universe.speed = value,
+            TuningField::CollectorVision => self.universe.vision = value,
+            TuningField::Damping => {
+                self.universe.damping = value;
+                self.apply_velocity();
+            }
+            TuningField::MinSpeed => {
+                self.universe.min_speed = value;
+                self.apply_velocity();
+            }
+            TuningField::TickRate => self.tick_rate.0 = value,
+            TuningField::MergeChance => self.merging.chance = value,
+            TuningField::MergeMaxMass => self.merging.max_mass = value,
+            TuningField::Lifetime => self.decay.lifetime = value,
+        }
+    }
+
+    pub fn preset(&self) -> TuningPreset {
+        TuningPreset::capture(
+            &self.universe,
+            &self.params,
+            &self.tick_rate,
+            &self.merging,
+            &self.decay,
+        )
+    }
+
+    pub fn apply_preset(&mut self, preset: &TuningPreset) {
+        preset.apply(
+            &mut self.universe,
+            &mut self.params,
+            &mut self.tick_rate,
+            &mut self.merging,
+            &mut self.decay,
+        );
+        self.apply_velocity();
+    }
+
+    // debri already in the world keep the damping they spawned with otherwise
+    fn apply_velocity(&mut self) {
+        for mut velocity in self.debri_query.iter_mut() {
+            velocity.damping = self.universe.damping;
+            velocity.min_speed = self.universe.min_speed;
+        }
+    }
+}
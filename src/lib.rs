@@ -0,0 +1,22 @@
+pub mod events;
+pub mod game;
+pub mod main_menu;
+pub mod quadtree;
+pub mod spatial;
+pub mod systems;
+
+use bevy::prelude::*;
+
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    Game,
+    GameOver,
+}
+
+/// A menu button's interaction and the colour it shows for it.
+pub type ButtonVisuals = (&'static Interaction, &'static mut BackgroundColor);
+
+/// Buttons marked with `B` whose interaction changed this frame.
+pub type ButtonChanged<B> = (Changed<Interaction>, With<B>);
@@ -1,14 +1,9 @@
-pub mod events;
-mod game;
-mod main_menu;
-mod quadtree;
-mod systems;
-
+use bevy_ball_game::game::GamePlugin;
+use bevy_ball_game::main_menu::MainMenuPlugin;
+use bevy_ball_game::AppState;
 use bevy_pancam::PanCamPlugin;
-use game::GamePlugin;
-use main_menu::MainMenuPlugin;
 
-use systems::*;
+use bevy_ball_game::systems::*;
 
 use bevy::prelude::*;
 
@@ -17,7 +12,7 @@ fn main() {
         // Bevy Plugins
         .add_plugins((
             DefaultPlugins.set(ImagePlugin::default_nearest()),
-            PanCamPlugin::default(),
+            PanCamPlugin,
         ))
         .add_state::<AppState>()
         // My Plugins
@@ -29,6 +24,7 @@ fn main() {
         .add_systems(
             Update,
             (
+                fit_camera_to_window,
                 transition_to_game_state,
                 transition_to_main_menu_state,
                 exit_game,
@@ -37,11 +33,3 @@ fn main() {
         )
         .run();
 }
-
-#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
-pub enum AppState {
-    #[default]
-    MainMenu,
-    Game,
-    GameOver,
-}
@@ -41,7 +41,7 @@ pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>)
                         text: Text {
                             sections: vec![TextSection::new(
                                 "Bevy Ball Game",
-                                get_title_text_style(&asset_server),
+                                get_title_text_style(asset_server),
                             )],
                             alignment: TextAlignment::Center,
                             ..default()
@@ -70,7 +70,7 @@ pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>)
                         text: Text {
                             sections: vec![TextSection::new(
                                 "Play",
-                                get_button_text_style(&asset_server),
+                                get_button_text_style(asset_server),
                             )],
                             alignment: TextAlignment::Center,
                             ..default()
@@ -93,7 +93,7 @@ pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>)
                         text: Text {
                             sections: vec![TextSection::new(
                                 "Quit",
-                                get_button_text_style(&asset_server),
+                                get_button_text_style(asset_server),
                             )],
                             alignment: TextAlignment::Center,
                             ..default()
@@ -0,0 +1,30 @@
+use super::{MAX_CELL_SIZE, MAX_DEPTH};
+
+/// What a `QuadTree` does with a region that doesn't fit inside its root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutOfBounds {
+    /// Double the root towards the region until it fits.
+    Grow,
+    /// Clamp the region to the root bounds.
+    Clamp,
+    /// Refuse the region with `QuadTreeError::OutOfBounds`.
+    Reject,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct QuadTreeConfig {
+    /// Values a leaf holds before it splits.
+    pub capacity: usize,
+    pub max_depth: usize,
+    pub out_of_bounds: OutOfBounds,
+}
+
+impl Default for QuadTreeConfig {
+    fn default() -> Self {
+        Self {
+            capacity: MAX_CELL_SIZE,
+            max_depth: MAX_DEPTH,
+            out_of_bounds: OutOfBounds::Grow,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuadTreeError {
+    /// The region lies outside the root and the tree rejects such regions.
+    OutOfBounds,
+    /// The region has NaN or infinite bounds.
+    InvalidRegion,
+    /// The id was removed, or the tree was cleared, since it was handed out.
+    StaleId,
+}
+
+impl fmt::Display for QuadTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuadTreeError::OutOfBounds => write!(f, "region is outside the quadtree bounds"),
+            QuadTreeError::InvalidRegion => write!(f, "region bounds are not finite"),
+            QuadTreeError::StaleId => write!(f, "slot id no longer refers to a value"),
+        }
+    }
+}
+
+impl std::error::Error for QuadTreeError {}
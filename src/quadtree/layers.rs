@@ -0,0 +1,45 @@
+use std::ops::{BitOr, BitOrAssign};
+
+/// Collision layers an entry lives on. Queries pass a mask and only see entries
+/// sharing at least one layer with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Layers(pub u32);
+
+impl Layers {
+    pub const NONE: Layers = Layers(0);
+    pub const DEBRI: Layers = Layers(1 << 0);
+    pub const COLLECTOR: Layers = Layers(1 << 1);
+    pub const ENEMY: Layers = Layers(1 << 2);
+    pub const BUILDING: Layers = Layers(1 << 3);
+    pub const PROJECTILE: Layers = Layers(1 << 4);
+    pub const ALL: Layers = Layers(u32::MAX);
+
+    pub fn intersects(self, mask: Layers) -> bool {
+        self.0 & mask.0 != 0
+    }
+
+    pub fn contains(self, other: Layers) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl Default for Layers {
+    /// Entries inserted without layers match every mask.
+    fn default() -> Self {
+        Layers::ALL
+    }
+}
+
+impl BitOr for Layers {
+    type Output = Layers;
+
+    fn bitor(self, rhs: Layers) -> Layers {
+        Layers(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Layers {
+    fn bitor_assign(&mut self, rhs: Layers) {
+        self.0 |= rhs.0;
+    }
+}
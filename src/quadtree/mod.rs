@@ -1,9 +1,14 @@
 pub const MAX_CELL_SIZE: usize = 10;
 pub const MAX_DEPTH: usize = 7;
 
-pub mod coord;
+pub mod config;
+pub mod error;
+pub mod layers;
 pub mod region;
 pub mod slot_map;
 pub mod tree;
 
 mod node;
+
+#[cfg(test)]
+mod tests;
@@ -1,7 +1,13 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use bevy::math::Vec2;
+
+use crate::spatial::IndexStats;
+
 use super::{
+    config::QuadTreeConfig,
     region::Region,
     slot_map::{SlotId, SlotMap},
-    MAX_CELL_SIZE, MAX_DEPTH,
 };
 
 #[derive(Debug)]
@@ -10,6 +16,37 @@ enum NodeType {
     Parent(Box<[QuadNode; 4]>),
 }
 
+enum Candidate<'a> {
+    Node(&'a QuadNode),
+    Value(&'a SlotId),
+}
+
+// Min-heap entry for the best-first walk in `QuadNode::nearest`.
+struct Nearest<'a> {
+    distance: f32,
+    candidate: Candidate<'a>,
+}
+
+impl PartialEq for Nearest<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance == other.distance
+    }
+}
+
+impl Eq for Nearest<'_> {}
+
+impl PartialOrd for Nearest<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Nearest<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
 #[derive(Debug)]
 pub struct QuadNode {
     region: Region,
@@ -37,28 +74,157 @@ impl QuadNode {
         self.node_type = NodeType::Leaf
     }
 
-    pub fn query(
-        &self,
-        region: &Region,
+    /// Visits every id whose region passes `hits` exactly once. An id stored
+    /// in several leaves is only reported by the leaf owning its `anchor`
+    /// point, so no set of seen ids has to be built. `anchor` must return a
+    /// point inside the id's region that also passes `hits`.
+    pub fn for_each<'a, H, A, V>(
+        &'a self,
+        hits: &H,
+        anchor: &A,
+        root: &Region,
         region_store: &SlotMap<Region>,
-        exclude: &Vec<SlotId>,
-    ) -> Vec<&SlotId> {
+        visit: &mut V,
+    ) where
+        H: Fn(&Region) -> bool,
+        A: Fn(&Region) -> Vec2,
+        V: FnMut(&'a SlotId),
+    {
+        match &self.node_type {
+            NodeType::Leaf => {
+                for id in self.values.iter() {
+                    let region = region_store.get(id).unwrap();
+                    if hits(region) && self.owns(anchor(region), root) {
+                        visit(id);
+                    }
+                }
+            }
+            NodeType::Parent(children) => {
+                for child in children.iter().filter(|child| hits(&child.region)) {
+                    child.for_each(hits, anchor, root, region_store, visit);
+                }
+            }
+        }
+    }
+
+    // Neighbouring leaves share their edges, so ownership is half open except
+    // along the far edges of the root.
+    fn owns(&self, point: Vec2, root: &Region) -> bool {
+        let region = &self.region;
+        point.x >= region.min.x
+            && (point.x < region.max.x || region.max.x >= root.max.x)
+            && point.y >= region.min.y
+            && (point.y < region.max.y || region.max.y >= root.max.y)
+    }
+
+    /// Collects the ids whose region passes `hits`. Children are only visited
+    /// when their own region passes it too.
+    pub fn query_by<F>(&self, hits: &F, region_store: &SlotMap<Region>) -> Vec<&SlotId>
+    where
+        F: Fn(&Region) -> bool,
+    {
         match &self.node_type {
             NodeType::Leaf => self
                 .values
                 .iter()
-                .filter(|id| {
-                    region_store.get(id).unwrap().intersects(region) && !exclude.contains(id)
-                })
+                .filter(|id| hits(region_store.get(id).unwrap()))
                 .collect(),
             NodeType::Parent(children) => children
                 .iter()
-                .filter(|child| child.region.intersects(region))
-                .flat_map(|child| child.query(region, region_store, exclude))
+                .filter(|child| hits(&child.region))
+                .flat_map(|child| child.query_by(hits, region_store))
                 .collect(),
         }
     }
 
+    /// Walks the nodes closest to `point` first and returns up to `k` ids
+    /// ordered by distance. Ids rejected by `filter` are skipped.
+    pub fn nearest<F>(
+        &self,
+        point: Vec2,
+        k: usize,
+        region_store: &SlotMap<Region>,
+        mut filter: F,
+    ) -> Vec<(&SlotId, f32)>
+    where
+        F: FnMut(&SlotId) -> bool,
+    {
+        let mut result: Vec<(&SlotId, f32)> = Vec::with_capacity(k);
+        if k == 0 {
+            return result;
+        }
+
+        let mut heap = BinaryHeap::new();
+        heap.push(Nearest {
+            distance: self.region.distance_to_point(point),
+            candidate: Candidate::Node(self),
+        });
+
+        while let Some(Nearest {
+            distance,
+            candidate,
+        }) = heap.pop()
+        {
+            match candidate {
+                Candidate::Value(id) => {
+                    // values spanning several leaves are pushed once per leaf
+                    if result.iter().any(|(found, _)| *found == id) {
+                        continue;
+                    }
+                    result.push((id, distance));
+                    if result.len() == k {
+                        break;
+                    }
+                }
+                Candidate::Node(node) => match &node.node_type {
+                    NodeType::Leaf => {
+                        for id in node.values.iter().filter(|id| filter(id)) {
+                            heap.push(Nearest {
+                                distance: region_store.get(id).unwrap().distance_to_point(point),
+                                candidate: Candidate::Value(id),
+                            });
+                        }
+                    }
+                    NodeType::Parent(children) => {
+                        for child in children.iter() {
+                            heap.push(Nearest {
+                                distance: child.region.distance_to_point(point),
+                                candidate: Candidate::Node(child),
+                            });
+                        }
+                    }
+                },
+            }
+        }
+
+        result
+    }
+
+    pub fn stats(&self, stats: &mut IndexStats) {
+        stats.nodes += 1;
+        stats.max_depth = stats.max_depth.max(self.depth);
+        match &self.node_type {
+            NodeType::Leaf => {
+                stats.leaves += 1;
+                stats.entries += self.values.len();
+            }
+            NodeType::Parent(children) => children.iter().for_each(|child| child.stats(stats)),
+        }
+    }
+
+    /// Calls `visit` with the region, ids and depth of every leaf.
+    pub fn for_each_leaf<'a, F>(&'a self, visit: &mut F)
+    where
+        F: FnMut(&'a Region, &'a [SlotId], usize),
+    {
+        match &self.node_type {
+            NodeType::Leaf => visit(&self.region, &self.values, self.depth),
+            NodeType::Parent(children) => {
+                children.iter().for_each(|child| child.for_each_leaf(visit))
+            }
+        }
+    }
+
     pub fn get_regions(&self) -> Vec<&Region> {
         match &self.node_type {
             NodeType::Leaf => vec![&self.region],
@@ -69,22 +235,116 @@ impl QuadNode {
         }
     }
 
-    pub fn remove(&mut self, value: &SlotId, region_store: &SlotMap<Region>) {
+    pub fn remove(&mut self, value: &SlotId, region: &Region, config: &QuadTreeConfig) {
         match &mut self.node_type {
             NodeType::Leaf => self.values.retain(|id| id != value),
             NodeType::Parent(children) => {
-                if let Some(region) = region_store.get(value) {
-                    children
-                        .iter_mut()
-                        .filter(|child| child.region.intersects(region))
-                        .for_each(|child| child.remove(value, region_store));
-                    if self.value_count_rec() < MAX_CELL_SIZE {
-                        let values = self.drain_values_rec();
-                        self.node_type = NodeType::Leaf;
-                        self.values.extend(values);
+                children
+                    .iter_mut()
+                    .filter(|child| child.region.intersects(region))
+                    .for_each(|child| child.remove(value, region, config));
+                self.merge_if_sparse(config);
+            }
+        }
+    }
+
+    /// Moves `value` from the leaves intersecting `old` to the leaves
+    /// intersecting `new`. Subtrees the value stays in are left untouched, so a
+    /// value that doesn't leave its leaf costs a walk down and nothing else.
+    /// `region_store` must already hold `new` for `value`.
+    pub fn relocate(
+        &mut self,
+        value: &SlotId,
+        old: &Region,
+        new: &Region,
+        region_store: &SlotMap<Region>,
+        config: &QuadTreeConfig,
+    ) {
+        if let NodeType::Parent(children) = &mut self.node_type {
+            let mut removed = false;
+            for child in children.iter_mut() {
+                match (child.region.intersects(old), child.region.intersects(new)) {
+                    (true, true) => child.relocate(value, old, new, region_store, config),
+                    (true, false) => {
+                        child.remove(value, old, config);
+                        removed = true;
                     }
+                    (false, true) => child.insert(value, region_store, config),
+                    (false, false) => {}
                 }
             }
+            if removed {
+                self.merge_if_sparse(config);
+            }
+        }
+    }
+
+    // Collapses the children back into a leaf once they hold too few values
+    // to be worth the split.
+    fn merge_if_sparse(&mut self, config: &QuadTreeConfig) {
+        if let NodeType::Parent(_) = self.node_type {
+            if self.value_count_rec() < config.capacity {
+                self.collapse();
+            }
+        }
+    }
+
+    // Turns this node into a leaf holding every value stored below it.
+    fn collapse(&mut self) {
+        let values = self.drain_values_rec();
+        self.node_type = NodeType::Leaf;
+        // values spanning several children were drained once per child
+        for value in values {
+            if !self.values.contains(&value) {
+                self.values.push(value);
+            }
+        }
+    }
+
+    /// Turns this node into a parent covering `bounds` and keeps the old node
+    /// as the quadrant it fills, so nothing below it is rebuilt. `bounds` must
+    /// be the old region grown by up to its own size on one side of each axis.
+    /// Values touching the three new quadrants are added to them.
+    pub fn grow(
+        &mut self,
+        bounds: Region,
+        region_store: &SlotMap<Region>,
+        config: &QuadTreeConfig,
+    ) {
+        let old = std::mem::replace(self, QuadNode::new(bounds, 0));
+        let inner = old.region;
+        // the old region's corner that isn't on the new bounds
+        let corner = Vec2::select(inner.min.cmpgt(bounds.min), inner.min, inner.max);
+
+        let mut children = bounds
+            .split_at(corner)
+            .map(|region| QuadNode::new(region, 1));
+        for child in children.iter_mut() {
+            if child.region != inner {
+                for (id, region) in region_store.iter_with_ids() {
+                    if child.region.intersects(region) {
+                        child.insert(&id, region_store, config);
+                    }
+                }
+            }
+        }
+        let slot = children
+            .iter()
+            .position(|child| child.region == inner)
+            .expect("bounds must be the old region doubled");
+        children[slot] = old;
+        children[slot].deepen(config);
+        self.node_type = NodeType::Parent(Box::new(children));
+    }
+
+    // Moves this subtree one level down, folding any node that reaches
+    // `max_depth` into a leaf.
+    fn deepen(&mut self, config: &QuadTreeConfig) {
+        self.depth += 1;
+        if self.depth >= config.max_depth {
+            self.collapse();
+        } else if let NodeType::Parent(children) = &mut self.node_type {
+            children.iter_mut().for_each(|child| child.deepen(config));
         }
     }
 
@@ -107,12 +367,17 @@ impl QuadNode {
         }
     }
 
-    pub fn insert(&mut self, value: &SlotId, region_store: &SlotMap<Region>) {
+    pub fn insert(
+        &mut self,
+        value: &SlotId,
+        region_store: &SlotMap<Region>,
+        config: &QuadTreeConfig,
+    ) {
         match &mut self.node_type {
             NodeType::Leaf => {
                 self.values.push(value.clone());
 
-                if self.values.len() > MAX_CELL_SIZE && self.depth < MAX_DEPTH {
+                if self.values.len() > config.capacity && self.depth < config.max_depth {
                     let _divide = self.region.quad_divide();
                     self.node_type = NodeType::Parent(Box::new(
                         self.region
@@ -121,14 +386,14 @@ impl QuadNode {
                     ));
                     let ids: Vec<SlotId> = self.values.drain(..).collect();
                     for id in ids {
-                        self.insert(&id, region_store);
+                        self.insert(&id, region_store, config);
                     }
                 }
             }
             NodeType::Parent(children) => children
                 .iter_mut()
                 .filter(|child| child.region.intersects(region_store.get(value).unwrap()))
-                .for_each(|child| child.insert(value, region_store)),
+                .for_each(|child| child.insert(value, region_store, config)),
         }
     }
 }
@@ -1,20 +1,24 @@
-use super::coord::Coord;
+use bevy::math::Vec2;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Region {
-    pub min: Coord,
-    pub max: Coord,
+    pub min: Vec2,
+    pub max: Vec2,
 }
 
 impl Region {
-    pub fn new(min: Coord, max: Coord) -> Self {
+    pub fn new(min: Vec2, max: Vec2) -> Self {
         Self { min, max }
     }
 
-    pub fn with_margin(&self, margin: i32) -> Self {
+    pub fn from_center(center: Vec2, half_size: Vec2) -> Self {
+        Self::new(center - half_size, center + half_size)
+    }
+
+    pub fn with_margin(&self, margin: f32) -> Self {
         Self::new(
-            Coord::new(self.min.x - margin, self.min.y - margin),
-            Coord::new(self.max.x + margin, self.max.y + margin),
+            self.min - Vec2::splat(margin),
+            self.max + Vec2::splat(margin),
         )
     }
 
@@ -25,31 +29,96 @@ impl Region {
             && self.max.y >= other.min.y
     }
 
-    pub fn size_f32(&self) -> (f32, f32) {
-        (
-            (self.max.x - self.min.x) as f32,
-            (self.max.y - self.min.y) as f32,
-        )
+    /// True if `other` lies entirely inside this region.
+    pub fn contains(&self, other: &Region) -> bool {
+        self.min.x <= other.min.x
+            && self.min.y <= other.min.y
+            && self.max.x >= other.max.x
+            && self.max.y >= other.max.y
     }
 
-    pub fn into_f32(&self) -> (f32, f32, f32, f32) {
-        (
-            self.min.x as f32,
-            self.min.y as f32,
-            self.max.x as f32,
-            self.max.y as f32,
+    /// Squeezes `other` into this region.
+    pub fn clamp(&self, other: &Region) -> Region {
+        Region::new(
+            other.min.clamp(self.min, self.max),
+            other.max.clamp(self.min, self.max),
         )
     }
 
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) / 2.0
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    pub fn area(&self) -> f32 {
+        let size = self.size();
+        size.x * size.y
+    }
+
+    /// Smallest region covering both `self` and `other`.
+    pub fn union(&self, other: &Region) -> Region {
+        Region::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// Distance from `point` to the closest point of the region, zero inside.
+    pub fn distance_to_point(&self, point: Vec2) -> f32 {
+        let outside = (self.min - point).max(point - self.max).max(Vec2::ZERO);
+        outside.length()
+    }
+
+    pub fn intersects_circle(&self, center: Vec2, radius: f32) -> bool {
+        self.distance_to_point(center) <= radius
+    }
+
+    /// Distance along `dir` at which a ray from `origin` enters the region,
+    /// or `None` if it misses within `max_dist`. `dir` must be normalized.
+    pub fn ray_intersection(&self, origin: Vec2, dir: Vec2, max_dist: f32) -> Option<f32> {
+        let mut enter = 0.0_f32;
+        let mut exit = max_dist;
+        for axis in 0..2 {
+            if dir[axis] == 0.0 {
+                // parallel to this slab, either always inside it or never
+                if origin[axis] < self.min[axis] || origin[axis] > self.max[axis] {
+                    return None;
+                }
+            } else {
+                let t1 = (self.min[axis] - origin[axis]) / dir[axis];
+                let t2 = (self.max[axis] - origin[axis]) / dir[axis];
+                enter = enter.max(t1.min(t2));
+                exit = exit.min(t1.max(t2));
+            }
+        }
+        (enter <= exit).then_some(enter)
+    }
+
     pub fn quad_divide(&self) -> [Region; 4] {
-        let diag = self.max - self.min;
-        let width = diag.project_x();
-        let height = diag.project_y();
+        self.split_at(self.center())
+    }
+
+    /// Splits into four quadrants meeting at `corner`, in the same order as
+    /// `quad_divide`.
+    pub fn split_at(&self, corner: Vec2) -> [Region; 4] {
         [
-            Region::new(self.min + height / 2, self.max - width / 2),
-            Region::new(self.min + diag / 2, self.max),
-            Region::new(self.min, self.min + diag / 2),
-            Region::new(self.min + width / 2, self.max - height / 2),
+            Region::new(
+                Vec2::new(self.min.x, corner.y),
+                Vec2::new(corner.x, self.max.y),
+            ),
+            Region::new(corner, self.max),
+            Region::new(self.min, corner),
+            Region::new(
+                Vec2::new(corner.x, self.min.y),
+                Vec2::new(self.max.x, corner.y),
+            ),
         ]
     }
 }
@@ -1,26 +1,34 @@
-
-
-#[derive(Debug, Clone, Hash, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct SlotId {
     index: u64,
+    generation: u32,
 }
 
-impl PartialEq for SlotId {
-    fn eq(&self, other: &Self) -> bool {
-        self.index == other.index
+impl SlotId {
+    pub fn new(index: u64, generation: u32) -> Self {
+        Self { index, generation }
     }
 }
 
-impl SlotId {
-    pub fn new(index: u64) -> Self {
-        Self { index }
-    }
+#[derive(Debug)]
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
 }
 
+/// Stores values in reusable slots. Every time a slot is freed its generation
+/// is bumped, so ids handed out before the removal no longer resolve.
 #[derive(Debug)]
 pub struct SlotMap<T> {
-    data: Vec<Option<T>>,
-    id_stack: Vec<SlotId>,
+    data: Vec<Slot<T>>,
+    id_stack: Vec<usize>,
+    len: usize,
+}
+
+impl<T> Default for SlotMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T> SlotMap<T> {
@@ -28,52 +36,120 @@ impl<T> SlotMap<T> {
         Self {
             data: Vec::new(),
             id_stack: Vec::new(),
+            len: 0,
         }
     }
 
     pub fn insert(&mut self, value: T) -> SlotId {
+        self.len += 1;
         match self.id_stack.pop() {
-            Some(id) => {
-                let cell = &mut self.data[id.index as usize];
-                *cell = Some(value);
-                id
+            Some(index) => {
+                let slot = &mut self.data[index];
+                slot.value = Some(value);
+                SlotId::new(index as u64, slot.generation)
             }
             None => {
-                let id = SlotId::new(self.data.len() as u64);
-                self.data.push(Some(value));
+                let id = SlotId::new(self.data.len() as u64, 0);
+                self.data.push(Slot {
+                    generation: 0,
+                    value: Some(value),
+                });
                 id
             }
         }
     }
 
+    /// Number of live values.
     pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of slots allocated, live or free.
+    pub fn capacity(&self) -> usize {
         self.data.len()
     }
 
     pub fn clear(&mut self) {
-        self.data.clear();
-        self.id_stack.clear();
+        // keep the slots so ids from before the clear stay stale
+        for (index, slot) in self.data.iter_mut().enumerate() {
+            if slot.value.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+                self.id_stack.push(index);
+            }
+        }
+        self.len = 0;
+    }
+
+    fn slot(&self, id: &SlotId) -> Option<&Slot<T>> {
+        self.data
+            .get(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
+    }
+
+    fn slot_mut(&mut self, id: &SlotId) -> Option<&mut Slot<T>> {
+        self.data
+            .get_mut(id.index as usize)
+            .filter(|slot| slot.generation == id.generation)
+    }
+
+    pub fn contains(&self, id: &SlotId) -> bool {
+        self.get(id).is_some()
     }
 
     pub fn get(&self, id: &SlotId) -> Option<&T> {
-        self.data[id.index as usize].as_ref()
+        self.slot(id).and_then(|slot| slot.value.as_ref())
     }
 
     pub fn get_mut(&mut self, id: &SlotId) -> Option<&mut T> {
-        self.data[id.index as usize].as_mut()
+        self.slot_mut(id).and_then(|slot| slot.value.as_mut())
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &T> {
-        self.data.iter().flatten()
+        self.data.iter().filter_map(|slot| slot.value.as_ref())
+    }
+
+    pub fn iter_with_ids(&self) -> impl Iterator<Item = (SlotId, &T)> {
+        self.data.iter().enumerate().filter_map(|(index, slot)| {
+            slot.value
+                .as_ref()
+                .map(|value| (SlotId::new(index as u64, slot.generation), value))
+        })
     }
 
-    pub fn update(&mut self, id: &SlotId, value: T) {
-        self.data[id.index as usize] = Some(value);
+    /// Replaces the value behind a live id and returns the previous one.
+    /// Stale ids are left alone and give back `None`.
+    pub fn update(&mut self, id: &SlotId, value: T) -> Option<T> {
+        self.get_mut(id).map(|cell| std::mem::replace(cell, value))
     }
 
     pub fn remove(&mut self, id: SlotId) -> Option<T> {
-        let idx = id.index as usize;
-        self.id_stack.push(id);
-        self.data[idx].take()
+        let slot = self.slot_mut(&id)?;
+        let value = slot.value.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.id_stack.push(id.index as usize);
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Removes every value for which `keep` returns false.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&SlotId, &mut T) -> bool,
+    {
+        for (index, slot) in self.data.iter_mut().enumerate() {
+            let id = SlotId::new(index as u64, slot.generation);
+            if let Some(value) = slot.value.as_mut() {
+                if !keep(&id, value) {
+                    slot.value = None;
+                    slot.generation = slot.generation.wrapping_add(1);
+                    self.id_stack.push(index);
+                    self.len -= 1;
+                }
+            }
+        }
     }
 }
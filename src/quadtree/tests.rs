@@ -0,0 +1,322 @@
+use std::collections::HashSet;
+
+use bevy::math::Vec2;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{
+    config::{OutOfBounds, QuadTreeConfig},
+    error::QuadTreeError,
+    region::Region,
+    slot_map::{SlotId, SlotMap},
+    tree::QuadTree,
+    MAX_DEPTH,
+};
+use crate::spatial::oracle::{check_queries, random_region, Oracle};
+
+const SEEDS: [u64; 4] = [1, 7, 42, 1337];
+const WORLD: f32 = 1000.0;
+
+fn world() -> Region {
+    Region::new(Vec2::ZERO, Vec2::splat(WORLD))
+}
+
+/// Where queries are probed from, a little past the world's edges.
+fn probes() -> Region {
+    world().with_margin(50.0)
+}
+
+/// Checks the structure of `tree` against `oracle`: every leaf is within the
+/// depth limit and holds each id once, every stored id is live and overlaps
+/// the leaf it is in, and every live id sits in all leaves it overlaps.
+fn check_invariants(tree: &QuadTree<u32>, oracle: &Oracle) {
+    let max_depth = tree.config().max_depth;
+    let mut seen = HashSet::new();
+
+    tree.for_each_leaf(|leaf, ids, depth| {
+        assert!(depth <= max_depth, "leaf at depth {depth} > {max_depth}");
+
+        let unique: HashSet<&SlotId> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len(), "duplicate id in leaf {leaf:?}");
+
+        for id in ids {
+            let (region, _) = oracle.entries.get(id).expect("leaf holds a dead id");
+            assert!(region.intersects(leaf), "{region:?} stored in {leaf:?}");
+            seen.insert(id.clone());
+        }
+
+        for (id, (region, _)) in oracle.entries.iter() {
+            if region.intersects(leaf) {
+                assert!(ids.contains(id), "{region:?} missing from {leaf:?}");
+            }
+        }
+    });
+
+    assert_eq!(seen.len(), oracle.entries.len(), "ids lost from the tree");
+    assert_eq!(tree.value_count(), oracle.entries.len());
+}
+
+fn run_random_ops(seed: u64, config: QuadTreeConfig, ops: usize) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut tree = QuadTree::new(world(), config);
+    let mut oracle = Oracle::default();
+    let mut next_value = 0;
+
+    for step in 0..ops {
+        match rng.gen_range(0..10) {
+            0..=4 => {
+                let region = random_region(&mut rng, &world());
+                let id = tree.insert(region, next_value).unwrap();
+                assert!(
+                    !oracle.entries.contains_key(&id),
+                    "live id handed out twice"
+                );
+                oracle.entries.insert(id, (region, next_value));
+                next_value += 1;
+            }
+            5..=6 => {
+                if let Some(id) = oracle.random_id(&mut rng) {
+                    let (_, value) = oracle.entries.remove(&id).unwrap();
+                    assert_eq!(tree.remove(&id), Some(value));
+                    assert_eq!(tree.remove(&id), None);
+                }
+            }
+            _ => {
+                if let Some(id) = oracle.random_id(&mut rng) {
+                    let (old, _) = oracle.entries[&id];
+                    // mostly small moves like a flocking tick, sometimes a jump
+                    let region = if rng.gen_bool(0.8) {
+                        let x = rng.gen_range(-5.0..5.0);
+                        let y = rng.gen_range(-5.0..5.0);
+                        Region::new(old.min + Vec2::new(x, y), old.max + Vec2::new(x, y))
+                    } else {
+                        random_region(&mut rng, &world())
+                    };
+                    let region = world().clamp(&region);
+                    tree.update(&id, region, next_value).unwrap();
+                    oracle.entries.insert(id, (region, next_value));
+                    next_value += 1;
+                }
+            }
+        }
+
+        if step % 50 == 0 {
+            check_invariants(&tree, &oracle);
+            check_queries(&tree, &oracle, &probes(), &mut rng);
+        }
+    }
+    check_invariants(&tree, &oracle);
+    check_queries(&tree, &oracle, &probes(), &mut rng);
+}
+
+#[test]
+fn random_ops_match_oracle() {
+    for seed in SEEDS {
+        run_random_ops(seed, QuadTreeConfig::default(), 2000);
+    }
+}
+
+#[test]
+fn random_ops_match_oracle_with_small_leaves() {
+    let config = QuadTreeConfig {
+        capacity: 1,
+        max_depth: 4,
+        ..Default::default()
+    };
+    for seed in SEEDS {
+        run_random_ops(seed, config, 1000);
+    }
+}
+
+#[test]
+fn depth_never_exceeds_max_depth() {
+    let mut tree = QuadTree::new(world(), QuadTreeConfig::default());
+    let region = Region::from_center(Vec2::splat(333.3), Vec2::splat(0.5));
+    let ids: Vec<SlotId> = (0..200).map(|i| tree.insert(region, i).unwrap()).collect();
+
+    assert!(tree.stats().max_depth <= MAX_DEPTH);
+    assert_eq!(tree.query(&region).len(), ids.len());
+}
+
+#[test]
+fn growing_keeps_the_old_root_as_a_quadrant() {
+    let config = QuadTreeConfig {
+        capacity: 1,
+        max_depth: 4,
+        ..Default::default()
+    };
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut tree = QuadTree::new(world(), config);
+    let mut oracle = Oracle::default();
+    for value in 0..200 {
+        let region = random_region(&mut rng, &world());
+        let id = tree.insert(region, value).unwrap();
+        oracle.entries.insert(id, (region, value));
+    }
+
+    // far enough out to double the root a few times, on every side
+    for (value, center) in [(200, 3.5), (201, -6.0), (202, 20.0)] {
+        let region = Region::from_center(Vec2::new(center * WORLD, -center * WORLD), Vec2::ONE);
+        let id = tree.insert(region, value).unwrap();
+        oracle.entries.insert(id, (region, value));
+        assert!(tree.size().contains(&region));
+
+        check_invariants(&tree, &oracle);
+        check_queries(&tree, &oracle, tree.size(), &mut rng);
+    }
+}
+
+#[test]
+fn removing_everything_collapses_the_root() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut tree = QuadTree::new(world(), QuadTreeConfig::default());
+    let ids: Vec<SlotId> = (0..500)
+        .map(|i| tree.insert(random_region(&mut rng, &world()), i).unwrap())
+        .collect();
+    assert!(tree.stats().nodes > 1);
+
+    for id in ids.iter() {
+        assert!(tree.remove(id).is_some());
+    }
+    let stats = tree.stats();
+    assert_eq!((stats.nodes, stats.leaves, stats.entries), (1, 1, 0));
+}
+
+#[test]
+fn quad_divide_covers_odd_regions() {
+    let regions = [
+        Region::new(Vec2::ZERO, Vec2::new(7.0, 5.0)),
+        Region::new(Vec2::new(-3.0, -1.0), Vec2::new(4.0, 6.0)),
+        Region::new(Vec2::ZERO, Vec2::ONE),
+        Region::new(Vec2::ZERO, Vec2::new(0.001, 3.0)),
+        Region::new(Vec2::new(-641.0, -359.0), Vec2::new(641.0, 359.0)),
+    ];
+    for region in regions {
+        let children = region.quad_divide();
+        let union = children
+            .iter()
+            .fold(children[0], |acc, child| acc.union(child));
+        assert_eq!(union, region);
+
+        let area: f32 = children.iter().map(Region::area).sum();
+        assert!((area - region.area()).abs() <= region.area() * 1e-5);
+
+        for child in children.iter() {
+            assert!(region.contains(child));
+            assert!(child.contains_point(region.center()));
+        }
+    }
+}
+
+#[test]
+fn values_on_split_lines_of_odd_regions_are_found_once() {
+    let root = Region::new(Vec2::ZERO, Vec2::new(7.0, 5.0));
+    let config = QuadTreeConfig {
+        capacity: 1,
+        ..Default::default()
+    };
+    let mut tree = QuadTree::new(root, config);
+
+    // points on the centre lines, edges and corners of the first few splits
+    let mut points = Vec::new();
+    for x in [0.0, 1.75, 3.5, 5.25, 7.0] {
+        for y in [0.0, 1.25, 2.5, 3.75, 5.0] {
+            points.push(Vec2::new(x, y));
+        }
+    }
+    for (value, point) in points.iter().enumerate() {
+        tree.insert(Region::new(*point, *point), value as u32)
+            .unwrap();
+    }
+
+    for (value, point) in points.iter().enumerate() {
+        let hits = tree.query(&Region::new(*point, *point));
+        assert_eq!(hits, vec![&(value as u32)], "point {point}");
+    }
+    assert_eq!(tree.query(&root).len(), points.len());
+}
+
+#[test]
+fn sub_pixel_regions_are_indexed() {
+    let mut tree = QuadTree::new(world(), QuadTreeConfig::default());
+    let region = Region::from_center(Vec2::new(10.3, 10.7), Vec2::splat(0.05));
+    tree.insert(region, 1).unwrap();
+
+    let miss = Region::from_center(Vec2::new(10.3, 10.5), Vec2::splat(0.05));
+    assert_eq!(tree.query(&region), vec![&1]);
+    assert!(tree.query(&miss).is_empty());
+    assert_eq!(tree.query_circle(Vec2::new(10.3, 10.6), 0.1), vec![&1]);
+}
+
+#[test]
+fn stale_ids_are_rejected() {
+    let mut tree = QuadTree::new(world(), QuadTreeConfig::default());
+    let region = Region::from_center(Vec2::splat(50.0), Vec2::ONE);
+    let id = tree.insert(region, 1).unwrap();
+    tree.remove(&id);
+    let reused = tree.insert(region, 2).unwrap();
+
+    assert_ne!(id, reused);
+    assert_eq!(tree.get(&id), None);
+    assert_eq!(tree.remove(&id), None);
+    assert_eq!(tree.update(&id, region, 3), Err(QuadTreeError::StaleId));
+    assert_eq!(tree.get(&reused), Some(&2));
+
+    tree.clear();
+    assert_eq!(tree.get(&reused), None);
+}
+
+#[test]
+fn slot_map_reuses_slots_with_new_generations() {
+    let mut map = SlotMap::new();
+    let a = map.insert("a");
+    let b = map.insert("b");
+    assert_eq!(map.remove(a.clone()), Some("a"));
+    let c = map.insert("c");
+
+    assert_eq!(map.capacity(), 2);
+    assert_eq!(map.len(), 2);
+    assert!(!map.contains(&a));
+    assert_eq!(map.get(&c), Some(&"c"));
+
+    map.retain(|id, _| *id != b);
+    assert_eq!(
+        map.iter_with_ids().map(|(id, _)| id).collect::<Vec<_>>(),
+        vec![c]
+    );
+
+    map.clear();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn out_of_bounds_policies() {
+    let outside = Region::from_center(Vec2::splat(WORLD * 3.0), Vec2::ONE);
+
+    let mut tree = QuadTree::new(world(), QuadTreeConfig::default());
+    tree.insert(outside, 1).unwrap();
+    assert!(tree.size().contains(&outside));
+    assert_eq!(tree.query(&outside), vec![&1]);
+
+    let clamp = QuadTreeConfig {
+        out_of_bounds: OutOfBounds::Clamp,
+        ..Default::default()
+    };
+    let mut tree = QuadTree::new(world(), clamp);
+    tree.insert(outside, 1).unwrap();
+    assert_eq!(*tree.size(), world());
+    assert_eq!(
+        tree.query(&Region::new(Vec2::splat(WORLD), Vec2::splat(WORLD))),
+        vec![&1]
+    );
+
+    let reject = QuadTreeConfig {
+        out_of_bounds: OutOfBounds::Reject,
+        ..Default::default()
+    };
+    let mut tree = QuadTree::new(world(), reject);
+    assert_eq!(tree.insert(outside, 1), Err(QuadTreeError::OutOfBounds));
+    assert_eq!(tree.value_count(), 0);
+
+    let nan = Region::new(Vec2::splat(f32::NAN), Vec2::ONE);
+    assert_eq!(tree.insert(nan, 1), Err(QuadTreeError::InvalidRegion));
+}
@@ -1,6 +1,12 @@
-use bevy::utils::{HashMap, HashSet};
+use bevy::{math::Vec2, utils::HashSet};
+use rayon::prelude::*;
+
+use crate::spatial::IndexStats;
 
 use super::{
+    config::{OutOfBounds, QuadTreeConfig},
+    error::QuadTreeError,
+    layers::Layers,
     node::QuadNode,
     region::Region,
     slot_map::{SlotId, SlotMap},
@@ -8,16 +14,20 @@ use super::{
 
 #[derive(Debug)]
 pub struct QuadTree<T> {
+    config: QuadTreeConfig,
     region_store: SlotMap<Region>,
     value_store: SlotMap<T>,
+    layer_store: SlotMap<Layers>,
     root: Box<QuadNode>,
 }
 
 impl<T> QuadTree<T> {
-    pub fn new(region: Region) -> Self {
+    pub fn new(region: Region, config: QuadTreeConfig) -> Self {
         Self {
+            config,
             region_store: SlotMap::new(),
             value_store: SlotMap::new(),
+            layer_store: SlotMap::new(),
             root: Box::new(QuadNode::new(region, 0)),
         }
     }
@@ -29,20 +39,124 @@ impl<T> QuadTree<T> {
     pub fn clear(&mut self) {
         self.region_store.clear();
         self.value_store.clear();
+        self.layer_store.clear();
         self.root.clear();
     }
 
-    pub fn query(&self, region: &Region, exclude: &Vec<SlotId>) -> Vec<&T> {
-        let _distinct_result: HashMap<SlotId, SlotId> = HashMap::new();
+    pub fn query(&self, region: &Region) -> Vec<&T> {
+        let mut result = Vec::new();
+        self.for_each_in(region, |_, value| result.push(value));
+        result
+    }
+
+    /// Returns the values intersecting `region` that live on a layer of
+    /// `mask` and pass `predicate`.
+    pub fn query_filtered<F>(&self, region: &Region, mask: Layers, predicate: F) -> Vec<&T>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut result = Vec::new();
+        self.for_each_in(region, |id, value| {
+            if self.layer_store.get(id).unwrap().intersects(mask) && predicate(value) {
+                result.push(value);
+            }
+        });
+        result
+    }
+
+    /// Calls `visit` once for every value whose region intersects `region`,
+    /// without allocating.
+    pub fn for_each_in<'a, F>(&'a self, region: &Region, mut visit: F)
+    where
+        F: FnMut(&'a SlotId, &'a T),
+    {
+        self.root.for_each(
+            &|other: &Region| other.intersects(region),
+            &|other: &Region| other.min.max(region.min),
+            self.root.size(),
+            &self.region_store,
+            &mut |id| visit(id, self.value_store.get(id).unwrap()),
+        );
+    }
+
+    /// Returns the values whose region overlaps the circle.
+    pub fn query_circle(&self, center: Vec2, radius: f32) -> Vec<&T> {
+        let mut result = Vec::new();
+        self.for_each_in_circle(center, radius, |_, value| result.push(value));
+        result
+    }
+
+    /// Calls `visit` once for every value whose region overlaps the circle,
+    /// without allocating.
+    pub fn for_each_in_circle<'a, F>(&'a self, center: Vec2, radius: f32, mut visit: F)
+    where
+        F: FnMut(&'a SlotId, &'a T),
+    {
+        self.root.for_each(
+            &|region: &Region| region.intersects_circle(center, radius),
+            &|region: &Region| center.clamp(region.min, region.max),
+            self.root.size(),
+            &self.region_store,
+            &mut |id| visit(id, self.value_store.get(id).unwrap()),
+        );
+    }
+
+    /// Casts a ray from `origin` along `dir` and returns every value it hits
+    /// within `max_dist`, together with the hit distance, closest first.
+    pub fn raycast(&self, origin: Vec2, dir: Vec2, max_dist: f32) -> Vec<(&T, f32)> {
+        let dir = dir.normalize_or_zero();
+        if dir == Vec2::ZERO {
+            return Vec::new();
+        }
 
         let set: HashSet<_> = self
             .root
-            .query(region, &self.region_store, exclude)
+            .query_by(
+                &|region: &Region| region.ray_intersection(origin, dir, max_dist).is_some(),
+                &self.region_store,
+            )
             .drain(..)
             .collect();
 
-        set.iter()
-            .map(|id| self.value_store.get(id).unwrap())
+        let mut hits: Vec<(&T, f32)> = set
+            .iter()
+            .map(|id| {
+                let distance = self
+                    .region_store
+                    .get(id)
+                    .unwrap()
+                    .ray_intersection(origin, dir, max_dist)
+                    .unwrap();
+                (self.value_store.get(id).unwrap(), distance)
+            })
+            .collect();
+        hits.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        hits
+    }
+
+    /// Returns the values crossed by the segment from `a` to `b`, ordered
+    /// from `a` onwards.
+    pub fn query_segment(&self, a: Vec2, b: Vec2) -> Vec<&T> {
+        self.raycast(a, b - a, a.distance(b))
+            .into_iter()
+            .map(|(value, _)| value)
+            .collect()
+    }
+
+    /// Returns the `k` values closest to `point` together with their distance,
+    /// nearest first. Values off the `mask` layers or for which `filter`
+    /// returns false are ignored.
+    pub fn nearest<F>(&self, point: Vec2, k: usize, mask: Layers, filter: F) -> Vec<(&T, f32)>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.root
+            .nearest(point, k, &self.region_store, |id| {
+                self.layer_store.get(id).unwrap().intersects(mask)
+                    && filter(self.value_store.get(id).unwrap())
+            })
+            .into_iter()
+            .map(|(id, distance)| (self.value_store.get(id).unwrap(), distance))
             .collect()
     }
 
@@ -50,23 +164,138 @@ impl<T> QuadTree<T> {
         self.root.size()
     }
 
-    pub fn remove(&mut self, id: &SlotId) {
-        self.root.remove(id, &self.region_store);
-        self.region_store.remove(id.clone());
-        self.value_store.remove(id.clone());
+    pub fn contains(&self, id: &SlotId) -> bool {
+        self.value_store.contains(id)
+    }
+
+    pub fn get(&self, id: &SlotId) -> Option<&T> {
+        self.value_store.get(id)
+    }
+
+    pub fn layers(&self, id: &SlotId) -> Option<Layers> {
+        self.layer_store.get(id).copied()
+    }
+
+    pub fn config(&self) -> &QuadTreeConfig {
+        &self.config
+    }
+
+    /// Removes the entry behind `id`. Stale ids are ignored and return `None`.
+    pub fn remove(&mut self, id: &SlotId) -> Option<T> {
+        let region = self.region_store.remove(id.clone())?;
+        self.root.remove(id, &region, &self.config);
+        self.layer_store.remove(id.clone());
+        self.value_store.remove(id.clone())
+    }
+
+    /// Replaces the region and value stored under `id`. The entry is only
+    /// moved between nodes when the new region leaves the leaves it was in.
+    /// On error the tree is left untouched.
+    pub fn update(&mut self, id: &SlotId, region: Region, value: T) -> Result<(), QuadTreeError> {
+        if !self.region_store.contains(id) {
+            return Err(QuadTreeError::StaleId);
+        }
+        let region = self.fit(region)?;
+        let old = self.region_store.update(id, region).unwrap();
+        self.value_store.update(id, value);
+
+        self.root
+            .relocate(id, &old, &region, &self.region_store, &self.config);
+        Ok(())
+    }
+
+    // Applies the out of bounds policy to a region about to be stored.
+    fn fit(&mut self, region: Region) -> Result<Region, QuadTreeError> {
+        if !region.min.is_finite() || !region.max.is_finite() {
+            return Err(QuadTreeError::InvalidRegion);
+        }
+        let bounds = *self.root.size();
+        if bounds.contains(&region) {
+            return Ok(region);
+        }
+        match self.config.out_of_bounds {
+            OutOfBounds::Grow => {
+                self.grow_to_fit(&region);
+                Ok(region)
+            }
+            OutOfBounds::Clamp => Ok(bounds.clamp(&region)),
+            OutOfBounds::Reject => Err(QuadTreeError::OutOfBounds),
+        }
+    }
+
+    // Doubles the root towards `region` until it fits. Each step keeps the old
+    // root as a quadrant of the new one, so stored values stay where they are.
+    fn grow_to_fit(&mut self, region: &Region) {
+        while !self.root.size().contains(region) {
+            let mut bounds = *self.root.size();
+            let size = bounds.size().max(Vec2::ONE);
+            if region.min.x < bounds.min.x {
+                bounds.min.x -= size.x;
+            } else {
+                bounds.max.x += size.x;
+            }
+            if region.min.y < bounds.min.y {
+                bounds.min.y -= size.y;
+            } else {
+                bounds.max.y += size.y;
+            }
+            self.root.grow(bounds, &self.region_store, &self.config);
+        }
+    }
+
+    pub fn stats(&self) -> IndexStats {
+        let mut stats = IndexStats::default();
+        self.root.stats(&mut stats);
+        stats
+    }
+
+    /// Calls `visit` with the region, ids and depth of every leaf.
+    pub fn for_each_leaf<'a, F>(&'a self, mut visit: F)
+    where
+        F: FnMut(&'a Region, &'a [SlotId], usize),
+    {
+        self.root.for_each_leaf(&mut visit)
     }
 
     pub fn get_regions(&self) -> Vec<&Region> {
         self.root.get_regions()
     }
 
-    pub fn insert(&mut self, region: Region, values: T) -> SlotId {
+    pub fn insert(&mut self, region: Region, values: T) -> Result<SlotId, QuadTreeError> {
+        self.insert_with_layers(region, Layers::default(), values)
+    }
+
+    pub fn insert_with_layers(
+        &mut self,
+        region: Region,
+        layers: Layers,
+        values: T,
+    ) -> Result<SlotId, QuadTreeError> {
+        let region = self.fit(region)?;
         let region_id = self.region_store.insert(region);
         let value_id = self.value_store.insert(values);
+        let layer_id = self.layer_store.insert(layers);
+
+        assert!(region_id == value_id && value_id == layer_id);
 
-        assert!(region_id == value_id);
+        self.root
+            .insert(&value_id, &self.region_store, &self.config);
+        Ok(value_id)
+    }
+}
 
-        self.root.insert(&value_id, &self.region_store);
-        value_id
+impl<T: Sync> QuadTree<T> {
+    /// Runs one region query per entry of `regions` on the rayon pool and
+    /// returns the hits in the same order. Every query only reads the tree, so
+    /// a `&QuadTree<T>` can be shared between threads whenever `T: Sync`.
+    pub fn par_query_batch(&self, regions: &[Region]) -> Vec<Vec<&T>> {
+        regions
+            .par_iter()
+            .map(|region| {
+                let mut hits = Vec::new();
+                self.for_each_in(region, |_, value| hits.push(value));
+                hits
+            })
+            .collect()
     }
 }
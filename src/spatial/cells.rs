@@ -0,0 +1,332 @@
+use bevy::{
+    math::{IVec2, Vec2},
+    utils::HashSet,
+};
+
+use super::{IndexStats, SpatialIndex};
+use crate::quadtree::{
+    error::QuadTreeError,
+    layers::Layers,
+    region::Region,
+    slot_map::{SlotId, SlotMap},
+};
+
+/// Buckets of a cell based index. `CellIndex` does the bookkeeping on top,
+/// implementors only decide how cells are laid out and stored.
+pub trait CellStore {
+    /// Inclusive range of cells covering `region`.
+    fn cell_range(&self, region: &Region) -> (IVec2, IVec2);
+
+    fn cell_region(&self, cell: IVec2) -> Region;
+
+    fn cell(&self, cell: IVec2) -> &[SlotId];
+
+    fn add(&mut self, cell: IVec2, id: &SlotId);
+
+    fn remove(&mut self, cell: IVec2, id: &SlotId);
+
+    /// Cells holding at least one value.
+    fn occupied(&self) -> Vec<IVec2>;
+
+    /// Cells currently allocated, empty or not.
+    fn cell_count(&self) -> usize;
+
+    fn clear(&mut self);
+}
+
+fn cells_in((min, max): (IVec2, IVec2)) -> impl Iterator<Item = IVec2> {
+    (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+}
+
+// Cells on the edges of the square `ring_min..=ring_max` that lie within
+// `lo..=hi`, each once: the top and bottom rows, then the sides between them.
+fn ring_cells(
+    (ring_min, ring_max): (IVec2, IVec2),
+    (lo, hi): (IVec2, IVec2),
+) -> impl Iterator<Item = IVec2> {
+    let (min, max) = (ring_min.max(lo), ring_max.min(hi));
+    let step = (ring_max - ring_min).max(IVec2::ONE);
+    let rows = (ring_min.y..=ring_max.y)
+        .step_by(step.y as usize)
+        .filter(move |y| (lo.y..=hi.y).contains(y))
+        .flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)));
+    let sides = (ring_min.x..=ring_max.x)
+        .step_by(step.x as usize)
+        .filter(move |x| (lo.x..=hi.x).contains(x))
+        .flat_map(move |x| {
+            (min.y.max(ring_min.y + 1)..=max.y.min(ring_max.y - 1)).map(move |y| IVec2::new(x, y))
+        });
+    rows.chain(sides)
+}
+
+pub struct CellIndex<T, C> {
+    cells: C,
+    bounds: Region,
+    /// What `bounds` started as, before values outside it grew it.
+    initial_bounds: Region,
+    region_store: SlotMap<Region>,
+    value_store: SlotMap<T>,
+    layer_store: SlotMap<Layers>,
+}
+
+impl<T, C: CellStore> CellIndex<T, C> {
+    pub fn with_cells(bounds: Region, cells: C) -> Self {
+        Self {
+            cells,
+            bounds,
+            initial_bounds: bounds,
+            region_store: SlotMap::new(),
+            value_store: SlotMap::new(),
+            layer_store: SlotMap::new(),
+        }
+    }
+
+    // Calls `visit` once per value whose region passes `hits` among the cells
+    // covering `area`. A value spanning several cells is only reported from
+    // the first cell it shares with the range, so no set is needed.
+    fn visit_cells<'a, F, V>(&'a self, area: &Region, hits: F, mut visit: V)
+    where
+        F: Fn(&Region) -> bool,
+        V: FnMut(&'a SlotId),
+    {
+        let range = self.cells.cell_range(area);
+        for cell in cells_in(range) {
+            for id in self.cells.cell(cell) {
+                let region = self.region_store.get(id).unwrap();
+                let (own_min, _) = self.cells.cell_range(region);
+                if cell != own_min.max(range.0) || !hits(region) {
+                    continue;
+                }
+                visit(id);
+            }
+        }
+    }
+
+    fn validate(region: &Region) -> Result<(), QuadTreeError> {
+        if region.min.is_finite() && region.max.is_finite() {
+            Ok(())
+        } else {
+            Err(QuadTreeError::InvalidRegion)
+        }
+    }
+}
+
+impl<T, C: CellStore> SpatialIndex<T> for CellIndex<T, C> {
+    fn insert_with_layers(
+        &mut self,
+        region: Region,
+        layers: Layers,
+        value: T,
+    ) -> Result<SlotId, QuadTreeError> {
+        Self::validate(&region)?;
+        let region_id = self.region_store.insert(region);
+        let value_id = self.value_store.insert(value);
+        let layer_id = self.layer_store.insert(layers);
+
+        assert!(region_id == value_id && value_id == layer_id);
+
+        for cell in cells_in(self.cells.cell_range(&region)) {
+            self.cells.add(cell, &value_id);
+        }
+        self.bounds = self.bounds.union(&region);
+        Ok(value_id)
+    }
+
+    fn update(&mut self, id: &SlotId, region: Region, value: T) -> Result<(), QuadTreeError> {
+        if !self.region_store.contains(id) {
+            return Err(QuadTreeError::StaleId);
+        }
+        Self::validate(&region)?;
+        let old = self.region_store.update(id, region).unwrap();
+        self.value_store.update(id, value);
+
+        let old_range = self.cells.cell_range(&old);
+        let new_range = self.cells.cell_range(&region);
+        if old_range != new_range {
+            for cell in cells_in(old_range) {
+                self.cells.remove(cell, id);
+            }
+            for cell in cells_in(new_range) {
+                self.cells.add(cell, id);
+            }
+        }
+        self.bounds = self.bounds.union(&region);
+        Ok(())
+    }
+
+    fn remove(&mut self, id: &SlotId) -> Option<T> {
+        let region = self.region_store.remove(id.clone())?;
+        for cell in cells_in(self.cells.cell_range(&region)) {
+            self.cells.remove(cell, id);
+        }
+        self.layer_store.remove(id.clone());
+        self.value_store.remove(id.clone())
+    }
+
+    fn contains(&self, id: &SlotId) -> bool {
+        self.value_store.contains(id)
+    }
+
+    fn layers(&self, id: &SlotId) -> Option<Layers> {
+        self.layer_store.get(id).copied()
+    }
+
+    fn query(&self, region: &Region) -> Vec<&T> {
+        let mut result = Vec::new();
+        self.for_each_in(region, &mut |_, value| result.push(value));
+        result
+    }
+
+    fn query_filtered(
+        &self,
+        region: &Region,
+        mask: Layers,
+        predicate: &dyn Fn(&T) -> bool,
+    ) -> Vec<&T> {
+        let mut result = Vec::new();
+        self.for_each_in(region, &mut |id, value| {
+            if self.layer_store.get(id).unwrap().intersects(mask) && predicate(value) {
+                result.push(value);
+            }
+        });
+        result
+    }
+
+    fn query_circle(&self, center: Vec2, radius: f32) -> Vec<&T> {
+        let mut result = Vec::new();
+        self.for_each_in_circle(center, radius, &mut |_, value| result.push(value));
+        result
+    }
+
+    fn for_each_in<'a>(&'a self, region: &Region, visit: &mut dyn FnMut(&'a SlotId, &'a T)) {
+        self.visit_cells(
+            region,
+            |other| other.intersects(region),
+            |id| visit(id, self.value_store.get(id).unwrap()),
+        );
+    }
+
+    fn for_each_in_circle<'a>(
+        &'a self,
+        center: Vec2,
+        radius: f32,
+        visit: &mut dyn FnMut(&'a SlotId, &'a T),
+    ) {
+        self.visit_cells(
+            &Region::from_center(center, Vec2::splat(radius)),
+            |region| region.intersects_circle(center, radius),
+            |id| visit(id, self.value_store.get(id).unwrap()),
+        );
+    }
+
+    // Searches rings of cells around `point`, stopping once the k-th best
+    // distance is closer than anything the next ring could hold.
+    fn nearest(
+        &self,
+        point: Vec2,
+        k: usize,
+        mask: Layers,
+        filter: &dyn Fn(&T) -> bool,
+    ) -> Vec<(&T, f32)> {
+        let mut found: Vec<(&SlotId, f32)> = Vec::new();
+        if k == 0 || self.value_store.is_empty() {
+            return Vec::new();
+        }
+
+        let (lo, hi) = self.cells.cell_range(&self.bounds);
+        let center = self
+            .cells
+            .cell_range(&Region::new(point, point))
+            .0
+            .clamp(lo, hi);
+        let mut seen = HashSet::new();
+
+        for ring in 0.. {
+            let ring_min = center - IVec2::splat(ring);
+            let ring_max = center + IVec2::splat(ring);
+            for cell in ring_cells((ring_min, ring_max), (lo, hi)) {
+                for id in self.cells.cell(cell) {
+                    if seen.insert(id)
+                        && self.layer_store.get(id).unwrap().intersects(mask)
+                        && filter(self.value_store.get(id).unwrap())
+                    {
+                        let region = self.region_store.get(id).unwrap();
+                        found.push((id, region.distance_to_point(point)));
+                    }
+                }
+            }
+
+            if ring_min.cmple(lo).all() && ring_max.cmpge(hi).all() {
+                break;
+            }
+            if found.len() >= k {
+                found.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+                let searched = self
+                    .cells
+                    .cell_region(ring_min)
+                    .union(&self.cells.cell_region(ring_max));
+                let reach = (point - searched.min)
+                    .min(searched.max - point)
+                    .min_element()
+                    .max(0.0);
+                if found[k - 1].1 <= reach {
+                    break;
+                }
+            }
+        }
+
+        found.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        found
+            .into_iter()
+            .take(k)
+            .map(|(id, distance)| (self.value_store.get(id).unwrap(), distance))
+            .collect()
+    }
+
+    fn clear(&mut self) {
+        self.region_store.clear();
+        self.value_store.clear();
+        self.layer_store.clear();
+        self.cells.clear();
+        self.bounds = self.initial_bounds;
+    }
+
+    fn size(&self) -> &Region {
+        &self.bounds
+    }
+
+    fn value_count(&self) -> usize {
+        self.value_store.len()
+    }
+
+    fn regions(&self) -> Vec<Region> {
+        self.cells
+            .occupied()
+            .into_iter()
+            .map(|cell| self.cells.cell_region(cell))
+            .collect()
+    }
+
+    fn for_each_leaf(&self, visit: &mut dyn FnMut(&Region, usize, usize)) {
+        for cell in self.cells.occupied() {
+            visit(
+                &self.cells.cell_region(cell),
+                0,
+                self.cells.cell(cell).len(),
+            );
+        }
+    }
+
+    fn stats(&self) -> IndexStats {
+        let occupied = self.cells.occupied();
+        IndexStats {
+            nodes: self.cells.cell_count(),
+            leaves: occupied.len(),
+            max_depth: 0,
+            entries: occupied
+                .iter()
+                .map(|cell| self.cells.cell(*cell).len())
+                .sum(),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::math::{IVec2, Vec2};
+
+use super::cells::{CellIndex, CellStore};
+use crate::quadtree::{region::Region, slot_map::SlotId};
+
+/// Fixed array of equally sized cells over `bounds`. Regions outside the
+/// bounds are kept in the border cells.
+pub struct GridCells {
+    origin: Vec2,
+    cell_size: f32,
+    dims: IVec2,
+    cells: Vec<Vec<SlotId>>,
+}
+
+impl GridCells {
+    pub fn new(bounds: Region, cell_size: f32) -> Self {
+        let dims = (bounds.size() / cell_size)
+            .ceil()
+            .as_ivec2()
+            .max(IVec2::ONE);
+        Self {
+            origin: bounds.min,
+            cell_size,
+            dims,
+            cells: vec![Vec::new(); (dims.x * dims.y) as usize],
+        }
+    }
+
+    fn index(&self, cell: IVec2) -> usize {
+        (cell.y * self.dims.x + cell.x) as usize
+    }
+
+    fn cell_of(&self, point: Vec2) -> IVec2 {
+        ((point - self.origin) / self.cell_size)
+            .floor()
+            .as_ivec2()
+            .clamp(IVec2::ZERO, self.dims - IVec2::ONE)
+    }
+}
+
+impl CellStore for GridCells {
+    fn cell_range(&self, region: &Region) -> (IVec2, IVec2) {
+        (self.cell_of(region.min), self.cell_of(region.max))
+    }
+
+    fn cell_region(&self, cell: IVec2) -> Region {
+        let min = self.origin + cell.as_vec2() * self.cell_size;
+        Region::new(min, min + Vec2::splat(self.cell_size))
+    }
+
+    fn cell(&self, cell: IVec2) -> &[SlotId] {
+        &self.cells[self.index(cell)]
+    }
+
+    fn add(&mut self, cell: IVec2, id: &SlotId) {
+        let index = self.index(cell);
+        self.cells[index].push(id.clone());
+    }
+
+    fn remove(&mut self, cell: IVec2, id: &SlotId) {
+        let index = self.index(cell);
+        self.cells[index].retain(|other| other != id);
+    }
+
+    fn occupied(&self) -> Vec<IVec2> {
+        (0..self.dims.y)
+            .flat_map(|y| (0..self.dims.x).map(move |x| IVec2::new(x, y)))
+            .filter(|cell| !self.cell(*cell).is_empty())
+            .collect()
+    }
+
+    fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn clear(&mut self) {
+        self.cells.iter_mut().for_each(|cell| cell.clear());
+    }
+}
+
+pub type Grid<T> = CellIndex<T, GridCells>;
+
+impl<T> CellIndex<T, GridCells> {
+    pub fn new(bounds: Region, cell_size: f32) -> Self {
+        Self::with_cells(bounds, GridCells::new(bounds, cell_size))
+    }
+}
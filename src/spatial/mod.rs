@@ -0,0 +1,235 @@
+pub mod cells;
+pub mod grid;
+pub mod spatial_hash;
+
+#[cfg(test)]
+pub mod oracle;
+#[cfg(test)]
+mod tests;
+
+use bevy::math::Vec2;
+use rayon::prelude::*;
+
+use crate::quadtree::{
+    error::QuadTreeError, layers::Layers, region::Region, slot_map::SlotId, tree::QuadTree,
+};
+
+/// Common interface of the spatial indexes, so callers can swap the backend
+/// without caring how values are bucketed.
+pub trait SpatialIndex<T> {
+    /// Inserts `value` on every layer, so it matches any query mask.
+    fn insert(&mut self, region: Region, value: T) -> Result<SlotId, QuadTreeError> {
+        self.insert_with_layers(region, Layers::default(), value)
+    }
+
+    fn insert_with_layers(
+        &mut self,
+        region: Region,
+        layers: Layers,
+        value: T,
+    ) -> Result<SlotId, QuadTreeError>;
+
+    fn update(&mut self, id: &SlotId, region: Region, value: T) -> Result<(), QuadTreeError>;
+
+    fn remove(&mut self, id: &SlotId) -> Option<T>;
+
+    fn contains(&self, id: &SlotId) -> bool;
+
+    fn layers(&self, id: &SlotId) -> Option<Layers>;
+
+    fn query(&self, region: &Region) -> Vec<&T>;
+
+    /// Values intersecting `region` that live on a layer of `mask` and pass
+    /// `predicate`.
+    fn query_filtered(
+        &self,
+        region: &Region,
+        mask: Layers,
+        predicate: &dyn Fn(&T) -> bool,
+    ) -> Vec<&T>;
+
+    fn query_circle(&self, center: Vec2, radius: f32) -> Vec<&T>;
+
+    /// Calls `visit` once per value intersecting `region`, without allocating.
+    fn for_each_in<'a>(&'a self, region: &Region, visit: &mut dyn FnMut(&'a SlotId, &'a T));
+
+    /// Calls `visit` once per value overlapping the circle, without allocating.
+    fn for_each_in_circle<'a>(
+        &'a self,
+        center: Vec2,
+        radius: f32,
+        visit: &mut dyn FnMut(&'a SlotId, &'a T),
+    );
+
+    fn nearest(
+        &self,
+        point: Vec2,
+        k: usize,
+        mask: Layers,
+        filter: &dyn Fn(&T) -> bool,
+    ) -> Vec<(&T, f32)>;
+
+    fn clear(&mut self);
+
+    /// Bounds covering every value stored so far.
+    fn size(&self) -> &Region;
+
+    fn value_count(&self) -> usize;
+
+    /// Regions of the buckets currently in use, for debug rendering.
+    fn regions(&self) -> Vec<Region>;
+
+    /// Calls `visit` with the region, depth and number of values of every
+    /// leaf bucket.
+    fn for_each_leaf(&self, visit: &mut dyn FnMut(&Region, usize, usize));
+
+    fn stats(&self) -> IndexStats;
+}
+
+/// Runs one region query per entry of `regions` on the rayon pool, for any
+/// backend. Queries take `&self`, so a `Sync` index is a read-only view that
+/// every worker can share while nothing writes to it.
+pub fn par_query_batch<'a, T: Sync>(
+    index: &'a (dyn SpatialIndex<T> + Sync),
+    regions: &[Region],
+) -> Vec<Vec<&'a T>> {
+    regions
+        .par_iter()
+        .map(|region| {
+            let mut hits = Vec::new();
+            index.for_each_in(region, &mut |_, value| hits.push(value));
+            hits
+        })
+        .collect()
+}
+
+/// Shape of an index at one point in time. For cell based indexes every cell
+/// counts as a node and every occupied cell as a leaf.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IndexStats {
+    pub nodes: usize,
+    pub leaves: usize,
+    pub max_depth: usize,
+    /// Entries summed over all leaves, counting values spanning several leaves
+    /// once per leaf.
+    pub entries: usize,
+}
+
+impl IndexStats {
+    pub fn values_per_leaf(&self) -> f32 {
+        if self.leaves == 0 {
+            0.0
+        } else {
+            self.entries as f32 / self.leaves as f32
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpatialBackend {
+    #[default]
+    QuadTree,
+    Grid,
+    SpatialHash,
+}
+
+impl SpatialBackend {
+    pub fn next(self) -> Self {
+        match self {
+            SpatialBackend::QuadTree => SpatialBackend::Grid,
+            SpatialBackend::Grid => SpatialBackend::SpatialHash,
+            SpatialBackend::SpatialHash => SpatialBackend::QuadTree,
+        }
+    }
+}
+
+impl<T> SpatialIndex<T> for QuadTree<T> {
+    fn insert_with_layers(
+        &mut self,
+        region: Region,
+        layers: Layers,
+        value: T,
+    ) -> Result<SlotId, QuadTreeError> {
+        QuadTree::insert_with_layers(self, region, layers, value)
+    }
+
+    fn update(&mut self, id: &SlotId, region: Region, value: T) -> Result<(), QuadTreeError> {
+        QuadTree::update(self, id, region, value)
+    }
+
+    fn remove(&mut self, id: &SlotId) -> Option<T> {
+        QuadTree::remove(self, id)
+    }
+
+    fn contains(&self, id: &SlotId) -> bool {
+        QuadTree::contains(self, id)
+    }
+
+    fn layers(&self, id: &SlotId) -> Option<Layers> {
+        QuadTree::layers(self, id)
+    }
+
+    fn query(&self, region: &Region) -> Vec<&T> {
+        QuadTree::query(self, region)
+    }
+
+    fn query_filtered(
+        &self,
+        region: &Region,
+        mask: Layers,
+        predicate: &dyn Fn(&T) -> bool,
+    ) -> Vec<&T> {
+        QuadTree::query_filtered(self, region, mask, predicate)
+    }
+
+    fn query_circle(&self, center: Vec2, radius: f32) -> Vec<&T> {
+        QuadTree::query_circle(self, center, radius)
+    }
+
+    fn for_each_in<'a>(&'a self, region: &Region, visit: &mut dyn FnMut(&'a SlotId, &'a T)) {
+        QuadTree::for_each_in(self, region, visit)
+    }
+
+    fn for_each_in_circle<'a>(
+        &'a self,
+        center: Vec2,
+        radius: f32,
+        visit: &mut dyn FnMut(&'a SlotId, &'a T),
+    ) {
+        QuadTree::for_each_in_circle(self, center, radius, visit)
+    }
+
+    fn nearest(
+        &self,
+        point: Vec2,
+        k: usize,
+        mask: Layers,
+        filter: &dyn Fn(&T) -> bool,
+    ) -> Vec<(&T, f32)> {
+        QuadTree::nearest(self, point, k, mask, filter)
+    }
+
+    fn clear(&mut self) {
+        QuadTree::clear(self)
+    }
+
+    fn size(&self) -> &Region {
+        QuadTree::size(self)
+    }
+
+    fn value_count(&self) -> usize {
+        QuadTree::value_count(self)
+    }
+
+    fn regions(&self) -> Vec<Region> {
+        self.get_regions().into_iter().copied().collect()
+    }
+
+    fn for_each_leaf(&self, visit: &mut dyn FnMut(&Region, usize, usize)) {
+        QuadTree::for_each_leaf(self, |region, ids, depth| visit(region, depth, ids.len()))
+    }
+
+    fn stats(&self) -> IndexStats {
+        QuadTree::stats(self)
+    }
+}
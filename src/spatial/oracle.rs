@@ -0,0 +1,116 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::math::Vec2;
+use rand::{rngs::StdRng, Rng};
+
+use super::SpatialIndex;
+use crate::quadtree::{layers::Layers, region::Region, slot_map::SlotId};
+
+/// A point anywhere in `area`.
+pub fn random_point(rng: &mut StdRng, area: &Region) -> Vec2 {
+    Vec2::new(
+        rng.gen_range(area.min.x..area.max.x),
+        rng.gen_range(area.min.y..area.max.y),
+    )
+}
+
+/// A region centred in `area`.
+pub fn random_region(rng: &mut StdRng, area: &Region) -> Region {
+    // mostly small bodies, sometimes one spanning several cells
+    let extent = if rng.gen_bool(0.9) { 20.0 } else { 120.0 };
+    let half_size = Vec2::new(rng.gen_range(0.0..extent), rng.gen_range(0.0..extent));
+    Region::from_center(random_point(rng, area), half_size)
+}
+
+/// Brute force reference: every live entry in a flat list.
+#[derive(Default)]
+pub struct Oracle {
+    pub entries: HashMap<SlotId, (Region, u32)>,
+}
+
+impl Oracle {
+    pub fn query(&self, region: &Region) -> Vec<u32> {
+        sorted(
+            self.entries
+                .values()
+                .filter(|(other, _)| other.intersects(region))
+                .map(|(_, value)| *value),
+        )
+    }
+
+    pub fn query_circle(&self, center: Vec2, radius: f32) -> Vec<u32> {
+        sorted(
+            self.entries
+                .values()
+                .filter(|(region, _)| region.intersects_circle(center, radius))
+                .map(|(_, value)| *value),
+        )
+    }
+
+    pub fn nearest(&self, point: Vec2, k: usize) -> Vec<f32> {
+        let mut distances: Vec<f32> = self
+            .entries
+            .values()
+            .map(|(region, _)| region.distance_to_point(point))
+            .collect();
+        distances.sort_by(|a, b| a.total_cmp(b));
+        distances.truncate(k);
+        distances
+    }
+
+    pub fn random_id(&self, rng: &mut StdRng) -> Option<SlotId> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let index = rng.gen_range(0..self.entries.len());
+        self.entries.keys().nth(index).cloned()
+    }
+}
+
+pub fn sorted(values: impl Iterator<Item = u32>) -> Vec<u32> {
+    let mut values: Vec<u32> = values.collect();
+    values.sort();
+    values
+}
+
+/// Runs random region, circle and nearest queries probed from `area` against
+/// both `index` and `oracle`, and checks no visitor reports an id twice.
+pub fn check_queries(
+    index: &dyn SpatialIndex<u32>,
+    oracle: &Oracle,
+    area: &Region,
+    rng: &mut StdRng,
+) {
+    assert_eq!(index.value_count(), oracle.entries.len());
+
+    for _ in 0..10 {
+        let region = random_region(rng, area).with_margin(rng.gen_range(0.0..100.0));
+        let mut ids = Vec::new();
+        index.for_each_in(&region, &mut |id, _| ids.push(id.clone()));
+        let unique: HashSet<&SlotId> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len(), "query reported an id twice");
+        assert_eq!(
+            sorted(index.query(&region).into_iter().copied()),
+            oracle.query(&region)
+        );
+
+        let center = random_point(rng, area);
+        let radius = rng.gen_range(0.0..150.0);
+        let mut ids = Vec::new();
+        index.for_each_in_circle(center, radius, &mut |id, _| ids.push(id.clone()));
+        let unique: HashSet<&SlotId> = ids.iter().collect();
+        assert_eq!(unique.len(), ids.len(), "circle query reported an id twice");
+        assert_eq!(
+            sorted(index.query_circle(center, radius).into_iter().copied()),
+            oracle.query_circle(center, radius)
+        );
+
+        let k = rng.gen_range(1..8);
+        let distances: Vec<f32> = index
+            .nearest(center, k, Layers::default(), &|_| true)
+            .into_iter()
+            .map(|(_, distance)| distance)
+            .collect();
+        assert_eq!(distances, oracle.nearest(center, k), "nearest to {center}");
+    }
+}
@@ -0,0 +1,74 @@
+use bevy::{
+    math::{IVec2, Vec2},
+    utils::HashMap,
+};
+
+use super::cells::{CellIndex, CellStore};
+use crate::quadtree::{region::Region, slot_map::SlotId};
+
+/// Unbounded grid that only allocates the cells values fall into.
+pub struct HashCells {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<SlotId>>,
+}
+
+impl HashCells {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell_of(&self, point: Vec2) -> IVec2 {
+        (point / self.cell_size).floor().as_ivec2()
+    }
+}
+
+impl CellStore for HashCells {
+    fn cell_range(&self, region: &Region) -> (IVec2, IVec2) {
+        (self.cell_of(region.min), self.cell_of(region.max))
+    }
+
+    fn cell_region(&self, cell: IVec2) -> Region {
+        let min = cell.as_vec2() * self.cell_size;
+        Region::new(min, min + Vec2::splat(self.cell_size))
+    }
+
+    fn cell(&self, cell: IVec2) -> &[SlotId] {
+        self.cells.get(&cell).map_or(&[], |ids| ids.as_slice())
+    }
+
+    fn add(&mut self, cell: IVec2, id: &SlotId) {
+        self.cells.entry(cell).or_default().push(id.clone());
+    }
+
+    fn remove(&mut self, cell: IVec2, id: &SlotId) {
+        if let Some(ids) = self.cells.get_mut(&cell) {
+            ids.retain(|other| other != id);
+            if ids.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+
+    fn occupied(&self) -> Vec<IVec2> {
+        self.cells.keys().copied().collect()
+    }
+
+    fn cell_count(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn clear(&mut self) {
+        self.cells.clear();
+    }
+}
+
+pub type SpatialHash<T> = CellIndex<T, HashCells>;
+
+impl<T> CellIndex<T, HashCells> {
+    pub fn new(bounds: Region, cell_size: f32) -> Self {
+        Self::with_cells(bounds, HashCells::new(cell_size))
+    }
+}
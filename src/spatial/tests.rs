@@ -0,0 +1,137 @@
+use bevy::math::Vec2;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{
+    grid::Grid,
+    oracle::{check_queries, random_region, Oracle},
+    spatial_hash::SpatialHash,
+    SpatialBackend, SpatialIndex,
+};
+use crate::quadtree::{config::QuadTreeConfig, layers::Layers, region::Region, tree::QuadTree};
+
+const SEEDS: [u64; 4] = [1, 7, 42, 1337];
+const WORLD: f32 = 1000.0;
+const CELL_SIZE: f32 = 40.0;
+// values and probes reach this far past the world on every side
+const OVERHANG: f32 = 300.0;
+
+const BACKENDS: [SpatialBackend; 3] = [
+    SpatialBackend::QuadTree,
+    SpatialBackend::Grid,
+    SpatialBackend::SpatialHash,
+];
+
+fn world() -> Region {
+    Region::new(Vec2::ZERO, Vec2::splat(WORLD))
+}
+
+fn area() -> Region {
+    world().with_margin(OVERHANG)
+}
+
+fn build(backend: SpatialBackend) -> Box<dyn SpatialIndex<u32>> {
+    match backend {
+        SpatialBackend::QuadTree => Box::new(QuadTree::new(world(), QuadTreeConfig::default())),
+        SpatialBackend::Grid => Box::new(Grid::new(world(), CELL_SIZE)),
+        SpatialBackend::SpatialHash => Box::new(SpatialHash::new(world(), CELL_SIZE)),
+    }
+}
+
+fn run_random_ops(backend: SpatialBackend, seed: u64, ops: usize) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut index = build(backend);
+    let mut oracle = Oracle::default();
+    let mut next_value = 0;
+
+    for step in 0..ops {
+        match rng.gen_range(0..10) {
+            0..=4 => {
+                let region = random_region(&mut rng, &area());
+                let id = index.insert(region, next_value).unwrap();
+                assert!(
+                    !oracle.entries.contains_key(&id),
+                    "live id handed out twice"
+                );
+                oracle.entries.insert(id, (region, next_value));
+                next_value += 1;
+            }
+            5..=6 => {
+                if let Some(id) = oracle.random_id(&mut rng) {
+                    let (_, value) = oracle.entries.remove(&id).unwrap();
+                    assert_eq!(index.remove(&id), Some(value));
+                    assert_eq!(index.remove(&id), None);
+                    assert!(!index.contains(&id));
+                }
+            }
+            _ => {
+                if let Some(id) = oracle.random_id(&mut rng) {
+                    let (old, _) = oracle.entries[&id];
+                    // mostly small moves like a flocking tick, sometimes a jump
+                    let region = if rng.gen_bool(0.8) {
+                        let x = rng.gen_range(-5.0..5.0);
+                        let y = rng.gen_range(-5.0..5.0);
+                        Region::new(old.min + Vec2::new(x, y), old.max + Vec2::new(x, y))
+                    } else {
+                        random_region(&mut rng, &area())
+                    };
+                    index.update(&id, region, next_value).unwrap();
+                    oracle.entries.insert(id, (region, next_value));
+                    next_value += 1;
+                }
+            }
+        }
+
+        if step % 50 == 0 {
+            check_queries(index.as_ref(), &oracle, &area(), &mut rng);
+        }
+    }
+    check_queries(index.as_ref(), &oracle, &area(), &mut rng);
+}
+
+#[test]
+fn every_backend_matches_oracle() {
+    for backend in BACKENDS {
+        for seed in SEEDS {
+            run_random_ops(backend, seed, 2000);
+        }
+    }
+}
+
+#[test]
+fn nearest_finds_values_outside_the_grid() {
+    // the border cells of a grid also hold everything beyond them, so the
+    // ring search must not stop at a border cell's own extent
+    let far = Vec2::new(WORLD + 500.0, WORLD / 2.0);
+    for backend in BACKENDS {
+        let mut index = build(backend);
+        index
+            .insert(Region::from_center(far, Vec2::ONE), 1)
+            .unwrap();
+        index
+            .insert(
+                Region::from_center(Vec2::new(WORLD - 100.0, WORLD / 2.0), Vec2::ONE),
+                2,
+            )
+            .unwrap();
+
+        let probe = far - Vec2::X * 10.0;
+        let nearest = index.nearest(probe, 1, Layers::default(), &|_| true);
+        assert_eq!(nearest[0].0, &1, "{backend:?}");
+
+        let nearest = index.nearest(Vec2::new(-400.0, -400.0), 2, Layers::default(), &|_| true);
+        assert_eq!(nearest.len(), 2, "{backend:?}");
+    }
+}
+
+#[test]
+fn clearing_a_cell_index_forgets_grown_bounds() {
+    for backend in [SpatialBackend::Grid, SpatialBackend::SpatialHash] {
+        let mut index = build(backend);
+        let far = Region::from_center(Vec2::splat(WORLD * 3.0), Vec2::ONE);
+        index.insert(far, 1).unwrap();
+        assert!(index.size().contains(&far), "{backend:?}");
+
+        index.clear();
+        assert_eq!(*index.size(), world(), "{backend:?}");
+    }
+}
@@ -1,24 +1,67 @@
 use bevy::app::AppExit;
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
+use bevy::render::camera::{ScalingMode, Viewport};
+use bevy::window::{PrimaryWindow, WindowResized};
 use bevy_pancam::PanCam;
 
 use crate::events::*;
+use crate::game::resources::{CameraFit, WorldConfig};
 use crate::AppState;
 
-pub fn spawn_camera(mut commands: Commands, window_query: Query<&Window, With<PrimaryWindow>>) {
-    let window = window_query.get_single().unwrap();
+pub fn spawn_camera(mut commands: Commands, config: Res<WorldConfig>) {
+    let center = config.center();
+    let mut camera = Camera2dBundle {
+        transform: Transform::from_xyz(center.x, center.y, 0.0),
+        ..default()
+    };
+    // the whole world stays in view whatever the window's size
+    camera.projection.scaling_mode = ScalingMode::AutoMin {
+        min_width: config.size.x,
+        min_height: config.size.y,
+    };
 
-    commands
-        .spawn(Camera2dBundle {
-            transform: Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 0.0),
-            ..default()
-        })
-        .insert(PanCam {
-            min_scale: 1.,
-            max_scale: Some(5.),
-            ..PanCam::default()
-        });
+    commands.spawn(camera).insert(PanCam {
+        min_scale: 1.,
+        max_scale: Some(5.),
+        ..PanCam::default()
+    });
+}
+
+/// Letterboxes the camera to the world's shape when the window is resized.
+/// Only the camera changes, the world keeps its size.
+pub fn fit_camera_to_window(
+    mut resized: EventReader<WindowResized>,
+    config: Res<WorldConfig>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<&mut Camera, With<PanCam>>,
+) {
+    if resized.read().last().is_none() && !config.is_changed() {
+        return;
+    }
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    // minimised
+    if window.physical_width() == 0 || window.physical_height() == 0 {
+        return;
+    }
+    let viewport = match config.fit {
+        CameraFit::Fit => None,
+        CameraFit::Letterbox => {
+            let (position, size) = config.letterbox(UVec2::new(
+                window.physical_width(),
+                window.physical_height(),
+            ));
+            Some(Viewport {
+                physical_position: position,
+                physical_size: size,
+                ..default()
+            })
+        }
+    };
+    for mut camera in camera_query.iter_mut() {
+        camera.viewport = viewport.clone();
+    }
 }
 
 pub fn transition_to_game_state(
@@ -26,11 +69,9 @@ pub fn transition_to_game_state(
     app_state: Res<State<AppState>>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::G) {
-        if app_state.get() != &AppState::Game {
-            app_state_next_state.set(AppState::Game);
-            println!("Entered AppState::Game");
-        }
+    if keyboard_input.just_pressed(KeyCode::G) && app_state.get() != &AppState::Game {
+        app_state_next_state.set(AppState::Game);
+        println!("Entered AppState::Game");
     }
 }
 
@@ -39,11 +80,9 @@ pub fn transition_to_main_menu_state(
     app_state: Res<State<AppState>>,
     mut app_state_next_state: ResMut<NextState<AppState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::M) {
-        if app_state.get() != &AppState::MainMenu {
-            app_state_next_state.set(AppState::MainMenu);
-            println!("Entered AppState::MainMenu");
-        }
+    if keyboard_input.just_pressed(KeyCode::M) && app_state.get() != &AppState::MainMenu {
+        app_state_next_state.set(AppState::MainMenu);
+        println!("Entered AppState::MainMenu");
     }
 }
 
@@ -52,7 +91,7 @@ pub fn handle_game_over(
     mut app_state_next_state: ResMut<NextState<AppState>>,
 ) {
     for event in game_over_event_reader.read() {
-        println!("Your final score is: {}", event.score.to_string());
+        println!("Your final score is: {}", event.score);
         app_state_next_state.set(AppState::GameOver);
         println!("Entered AppState::GameOver");
     }